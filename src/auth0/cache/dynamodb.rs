@@ -16,7 +16,7 @@ use super::{Cache, CacheError};
 #[derive(thiserror::Error, Debug)]
 pub enum DynamoDBCacheError {
     #[error("AWS error when interacting with dynamo cache: {0}")]
-    Aws(Box<dyn Error>),
+    Aws(Box<dyn Error + Send + Sync>),
     #[error("Data in database is wrong. Key: {0}")]
     SchemaError(String),
 }
@@ -8,8 +8,6 @@ use std::error::Error;
 
 use crate::auth0::Token;
 
-mod crypto;
-
 #[cfg(feature = "cache-dynamodb")]
 #[cfg_attr(docsrs, doc(cfg(feature = "cache-dynamodb")))]
 pub mod dynamodb;
@@ -20,7 +18,7 @@ const TOKEN_PREFIX: &str = "auth0rs_tokens";
 
 #[derive(thiserror::Error, Debug)]
 #[error(transparent)]
-pub struct CacheError(pub Box<dyn Error>);
+pub struct CacheError(pub Box<dyn Error + Send + Sync>);
 
 #[async_trait::async_trait]
 pub trait Cache: Send + Sync + std::fmt::Debug {
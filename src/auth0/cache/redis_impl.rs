@@ -2,8 +2,9 @@ use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 
 use super::CacheError;
-use crate::auth0::cache::{crypto, Cache};
+use crate::auth0::cache::Cache;
 use crate::auth0::token::Token;
+use crate::crypto;
 
 #[derive(Debug, thiserror::Error)]
 pub enum RedisCacheError {
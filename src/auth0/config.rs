@@ -2,6 +2,7 @@ pub use super::StalenessCheckPercentage;
 use std::time::Duration;
 
 use reqwest::Url;
+use serde::Deserialize;
 
 #[derive(Clone)]
 pub struct Config {
@@ -100,7 +101,10 @@ impl Config {
 }
 
 // Eg. `Redis("redis://{host}:{port}?{ParamKey1}={ParamKey2}")` or `Inmemory` for inmemory cache
-#[derive(Clone, Eq, PartialEq)]
+//
+// When deserialized the variant is selected by the `type` field, eg. `{ "type": "redis", "url": "...", "key_prefix": "..." }`
+#[derive(Clone, Eq, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum CacheType {
     Redis { url: String, key_prefix: String },
     Inmemory,
@@ -0,0 +1,63 @@
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use tracing::Instrument;
+
+use crate::errors::PrimaBridgeResult;
+use crate::{DeliverableRequest, Response};
+
+/// The results of a batch of requests sent with [send_all_streaming](crate::BridgeImpl::send_all_streaming).
+pub type BatchStream<'a> = Pin<Box<dyn Stream<Item = PrimaBridgeResult<Response>> + Send + 'a>>;
+
+/// The order in which a [BatchStream] yields the results of the requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOrder {
+    /// As soon as every request completes.
+    Completion,
+    /// In the order of the requests, holding back the results of the ones completed before the previous ones.
+    Input,
+}
+
+/// Sends the requests, at most `concurrency` at a time, within a span counting the ones that succeeded and
+/// failed.
+pub(crate) fn send_all<'a, R>(
+    requests: impl IntoIterator<Item = R>,
+    concurrency: usize,
+    order: BatchOrder,
+) -> BatchStream<'a>
+where
+    R: DeliverableRequest<'a> + Send,
+{
+    let requests: Vec<R> = requests.into_iter().collect();
+    let span = tracing::info_span!(
+        "prima_bridge.batch",
+        "otel.name" = "batch",
+        "prima_bridge.batch.size" = requests.len(),
+        "prima_bridge.batch.concurrency" = concurrency,
+        "prima_bridge.batch.succeeded" = 0,
+        "prima_bridge.batch.failed" = 0,
+    );
+
+    // The spans of the requests are children of the batch one
+    let sends = futures::stream::iter(requests).map({
+        let span = span.clone();
+        move |request| request.send().instrument(span.clone())
+    });
+    let results = match order {
+        BatchOrder::Completion => sends.buffer_unordered(concurrency.max(1)).boxed(),
+        BatchOrder::Input => sends.buffered(concurrency.max(1)).boxed(),
+    };
+
+    let mut succeeded = 0;
+    let mut failed = 0;
+    Box::pin(results.inspect(move |result| match result {
+        Ok(_) => {
+            succeeded += 1;
+            span.record("prima_bridge.batch.succeeded", succeeded);
+        }
+        Err(_) => {
+            failed += 1;
+            span.record("prima_bridge.batch.failed", failed);
+        }
+    }))
+}
@@ -1,5 +1,8 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
 use std::{sync::Arc, time::Duration};
 
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::Url;
 use reqwest_middleware::Middleware;
 
@@ -7,33 +10,104 @@ use reqwest_middleware::Middleware;
 use crate::auth0;
 #[cfg(feature = "auth0")]
 use crate::auth0::RefreshingToken;
-use crate::{Bridge, BridgeImpl, RedirectPolicy};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::coalescing::Coalescer;
+use crate::errors::BridgeBuildError;
+use crate::limit::{ConcurrencyLimiter, RateLimiter};
+use crate::load_balancer::LoadBalancer;
+use crate::sealed::Sealed;
+use crate::tls::{self, TlsIdentity, TlsVersion};
+use crate::{
+    Bridge, BridgeClient, BridgeImpl, CircuitBreakerPolicy, ConcurrencyLimit, IdempotencyKeys, LoadBalancingPolicy,
+    Proxy, RateLimit, RedirectPolicy, RequestCoalescing, RequestCompression, RetryPolicy,
+};
 
 pub type BridgeBuilder = BridgeBuilderInner<reqwest::ClientBuilder>;
 
+/// The builder of a bridge with middlewares, returned by [BridgeBuilder::with_middleware].
+pub type MiddlewareBridgeBuilder = BridgeBuilderInner<MiddlewareClientBuilder>;
+
+/// A builder of the HTTP client used by a [Bridge], either a plain [reqwest::ClientBuilder] or a
+/// [MiddlewareClientBuilder].
+pub trait HttpClientBuilder: Sealed {
+    #[doc(hidden)]
+    fn map_http_client(self, map: impl FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder) -> Self;
+}
+
+impl HttpClientBuilder for reqwest::ClientBuilder {
+    fn map_http_client(self, map: impl FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder) -> Self {
+        map(self)
+    }
+}
+
+/// The client builder of a bridge with middlewares.
+///
+/// The HTTP client is built, and wrapped by the middlewares, only when the bridge is built, so that it can
+/// still be configured after adding the middlewares.
+pub struct MiddlewareClientBuilder {
+    client: reqwest::ClientBuilder,
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl HttpClientBuilder for MiddlewareClientBuilder {
+    fn map_http_client(self, map: impl FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder) -> Self {
+        Self {
+            client: map(self.client),
+            ..self
+        }
+    }
+}
+
+impl MiddlewareClientBuilder {
+    fn build(self) -> Result<reqwest_middleware::ClientWithMiddleware, BridgeBuildError> {
+        Ok(reqwest_middleware::ClientWithMiddleware::new(
+            self.client.build()?,
+            self.middlewares,
+        ))
+    }
+}
+
 /// A builder for creating [Bridge] instances.
 pub struct BridgeBuilderInner<T> {
     inner: T,
     #[cfg(feature = "auth0")]
     auth0: Option<auth0::RefreshingToken>,
+    retry_policy: Option<RetryPolicy>,
+    circuit_breaker: Option<CircuitBreakerPolicy>,
+    rate_limit: Option<RateLimit>,
+    concurrency_limit: Option<ConcurrencyLimit>,
+    load_balancing: LoadBalancingPolicy,
+    default_headers: HeaderMap,
+    default_query_pairs: Vec<(String, String)>,
+    default_timeout: Duration,
+    max_response_bytes: Option<u64>,
+    request_compression: Option<RequestCompression>,
+    request_coalescing: Option<RequestCoalescing>,
+    idempotency_keys: Option<IdempotencyKeys>,
+    #[cfg(feature = "http-cache")]
+    http_cache: Option<crate::HttpCache>,
+    /// Whether options of the HTTP client, which [BridgeBuilder::with_client] can't apply, have been set.
+    client_options: bool,
+    /// The first error occurred while configuring the builder, returned when building.
+    error: Option<BridgeBuildError>,
 }
 
 impl<T> BridgeBuilderInner<T> {
     /// Adds Auth0 JWT authentication to the requests made by the [Bridge].
+    ///
+    /// If the token cannot be fetched the error is returned when building the bridge.
     #[cfg_attr(docsrs, doc(cfg(feature = "auth0")))]
     #[cfg(feature = "auth0")]
     #[deprecated(since = "0.21.0", note = "please use with_refreshing_token instead")]
     pub async fn with_auth0(self, config: auth0::Config) -> Self {
         let client: reqwest::Client = reqwest::Client::new();
         #[allow(deprecated)]
-        let auth0 = auth0::Auth0::new(&client, config)
-            .await
-            .expect("Failed to create auth0 bridge")
-            .refreshing_token();
-
-        Self {
-            auth0: Some(auth0),
-            ..self
+        match auth0::Auth0::new(&client, config).await {
+            Ok(auth0) => Self {
+                auth0: Some(auth0.refreshing_token()),
+                ..self
+            },
+            Err(error) => self.with_error(BridgeBuildError::Auth0(error)),
         }
     }
 
@@ -45,39 +119,233 @@ impl<T> BridgeBuilderInner<T> {
             ..self
         }
     }
-}
 
-impl BridgeBuilderInner<reqwest::ClientBuilder> {
-    pub(crate) fn create() -> Self {
+    /// Sets the [RetryPolicy] used by every request made by the [Bridge].
+    ///
+    /// It can be overridden on a single request with
+    /// [DeliverableRequest::with_retry_policy](crate::DeliverableRequest::with_retry_policy).
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
         Self {
-            inner: reqwest::ClientBuilder::new(),
-            #[cfg(feature = "auth0")]
-            auth0: None,
+            retry_policy: Some(retry_policy),
+            ..self
         }
     }
 
-    pub fn with_user_agent(self, user_agent: impl Into<String>) -> Self {
+    /// Adds a circuit breaker to the [Bridge], configured by the given [CircuitBreakerPolicy].
+    ///
+    /// The breaker is shared by all the clones of the built bridge. While it is open, requests fail
+    /// immediately with [PrimaBridgeError::CircuitOpen](crate::prelude::PrimaBridgeError::CircuitOpen).
+    pub fn with_circuit_breaker(self, policy: CircuitBreakerPolicy) -> Self {
         Self {
-            inner: self.inner.user_agent(user_agent.into().as_str()),
-            #[cfg(feature = "auth0")]
-            auth0: self.auth0,
+            circuit_breaker: Some(policy),
+            ..self
         }
     }
 
-    pub fn with_redirect_policy(self, policy: RedirectPolicy) -> Self {
+    /// Limits the rate of the requests made by the [Bridge], to stay within the quota of the called service.
+    ///
+    /// The limit is shared by all the clones of the built bridge, and applies to every attempt of a request.
+    pub fn with_rate_limit(self, rate_limit: RateLimit) -> Self {
         Self {
-            inner: self.inner.redirect(policy.into()),
-            #[cfg(feature = "auth0")]
-            auth0: self.auth0,
+            rate_limit: Some(rate_limit),
+            ..self
         }
     }
 
-    pub fn with_pool_max_idle_per_host(self, max: usize) -> Self {
+    /// Limits the number of requests the [Bridge] can have in flight at the same time.
+    ///
+    /// The limit is shared by all the clones of the built bridge.
+    pub fn with_max_concurrency(self, concurrency_limit: impl Into<ConcurrencyLimit>) -> Self {
+        Self {
+            concurrency_limit: Some(concurrency_limit.into()),
+            ..self
+        }
+    }
+
+    /// Sets how the requests are balanced across the endpoints of a bridge built with
+    /// [build_with_endpoints](BridgeBuilder::build_with_endpoints). Defaults to round robin.
+    pub fn with_load_balancing(self, load_balancing: LoadBalancingPolicy) -> Self {
+        Self { load_balancing, ..self }
+    }
+
+    /// Sets headers that are added to every request made by the [Bridge].
+    ///
+    /// A header set on a request with the same name takes precedence.
+    pub fn with_default_headers(self, headers: Vec<(HeaderName, HeaderValue)>) -> Self {
+        let mut default_headers = self.default_headers;
+        default_headers.extend(headers);
+        Self {
+            default_headers,
+            ..self
+        }
+    }
+
+    /// Sets query string parameters that are added to every request made by the [Bridge].
+    ///
+    /// A parameter set on a request with the same name takes precedence.
+    pub fn with_default_query_pairs<K, V>(self, pairs: Vec<(K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut default_query_pairs = self.default_query_pairs;
+        default_query_pairs.extend(pairs.into_iter().map(|(name, value)| (name.into(), value.into())));
+        Self {
+            default_query_pairs,
+            ..self
+        }
+    }
+
+    /// Sets the timeout of the requests made by the [Bridge] that don't set their own.
+    ///
+    /// Default is 60 seconds.
+    pub fn with_default_timeout(self, default_timeout: Duration) -> Self {
+        Self {
+            default_timeout,
+            ..self
+        }
+    }
+
+    /// Sets the maximum size of the response bodies received by the [Bridge], so that a misbehaving server
+    /// can't exhaust the memory. Larger responses fail with
+    /// [PrimaBridgeError::ResponseTooLarge](crate::prelude::PrimaBridgeError::ResponseTooLarge).
+    ///
+    /// It can be overridden on a single request with
+    /// [DeliverableRequest::with_max_response_bytes](crate::DeliverableRequest::with_max_response_bytes).
+    /// Default is no limit.
+    pub fn with_max_response_bytes(self, max_response_bytes: u64) -> Self {
+        Self {
+            max_response_bytes: Some(max_response_bytes),
+            ..self
+        }
+    }
+
+    /// Compresses the request bodies sent by the [Bridge] according to the given [RequestCompression].
+    /// Default is no compression.
+    pub fn with_request_compression(self, request_compression: RequestCompression) -> Self {
+        Self {
+            request_compression: Some(request_compression),
+            ..self
+        }
+    }
+
+    /// Coalesces the identical `GET` requests made at the same time by the [Bridge], as described by
+    /// [RequestCoalescing]. Default is no coalescing.
+    pub fn with_request_coalescing(self, request_coalescing: RequestCoalescing) -> Self {
+        Self {
+            request_coalescing: Some(request_coalescing),
+            ..self
+        }
+    }
+
+    /// Adds an `Idempotency-Key` header to the requests made by the [Bridge] with an unsafe method, as described
+    /// by [IdempotencyKeys]. Default is no key, unless set by the requests.
+    pub fn with_idempotency_keys(self, idempotency_keys: IdempotencyKeys) -> Self {
+        Self {
+            idempotency_keys: Some(idempotency_keys),
+            ..self
+        }
+    }
+
+    /// Caches the responses to the `GET` requests made by the [Bridge], as described by [HttpCache](crate::HttpCache).
+    /// Default is no caching.
+    #[cfg_attr(docsrs, doc(cfg(feature = "http-cache")))]
+    #[cfg(feature = "http-cache")]
+    pub fn with_http_cache(self, http_cache: crate::HttpCache) -> Self {
+        Self {
+            http_cache: Some(http_cache),
+            ..self
+        }
+    }
+
+    /// Keeps the first error occurred while configuring the builder, to be returned when building.
+    fn with_error(self, error: BridgeBuildError) -> Self {
         Self {
-            inner: self.inner.pool_max_idle_per_host(max),
+            error: self.error.or(Some(error)),
+            ..self
+        }
+    }
+
+    /// Replaces the inner client builder, keeping every other setting.
+    fn map_inner<U>(self, map: impl FnOnce(T) -> U) -> BridgeBuilderInner<U> {
+        BridgeBuilderInner {
+            inner: map(self.inner),
             #[cfg(feature = "auth0")]
             auth0: self.auth0,
+            retry_policy: self.retry_policy,
+            circuit_breaker: self.circuit_breaker,
+            rate_limit: self.rate_limit,
+            concurrency_limit: self.concurrency_limit,
+            load_balancing: self.load_balancing,
+            default_headers: self.default_headers,
+            default_query_pairs: self.default_query_pairs,
+            default_timeout: self.default_timeout,
+            max_response_bytes: self.max_response_bytes,
+            request_compression: self.request_compression,
+            request_coalescing: self.request_coalescing,
+            idempotency_keys: self.idempotency_keys,
+            #[cfg(feature = "http-cache")]
+            http_cache: self.http_cache,
+            client_options: self.client_options,
+            error: self.error,
+        }
+    }
+
+    /// Creates the bridge, building its client from the inner client builder.
+    fn try_into_bridge<C: BridgeClient>(
+        self,
+        mut endpoints: Vec<Url>,
+        build_client: impl FnOnce(T) -> Result<C, BridgeBuildError>,
+    ) -> Result<BridgeImpl<C>, BridgeBuildError> {
+        if let Some(error) = self.error {
+            return Err(error);
         }
+        if endpoints.is_empty() {
+            return Err(BridgeBuildError::NoEndpoints);
+        }
+
+        let mut seen = HashSet::new();
+        endpoints.retain(|endpoint| seen.insert(endpoint.clone()));
+        let endpoint = endpoints[0].clone();
+        let load_balancer = match endpoints.len() {
+            1 => None,
+            _ => Some(LoadBalancer::new(self.load_balancing, endpoints)),
+        };
+
+        Ok(BridgeImpl {
+            inner_client: build_client(self.inner)?,
+            endpoint,
+            load_balancer,
+            #[cfg(feature = "auth0")]
+            auth0_opt: self.auth0,
+            retry_policy: self.retry_policy,
+            circuit_breaker: self.circuit_breaker.map(CircuitBreaker::new),
+            rate_limiter: self.rate_limit.map(RateLimiter::new),
+            concurrency_limiter: self.concurrency_limit.map(ConcurrencyLimiter::new),
+            default_headers: self.default_headers,
+            default_query_pairs: self.default_query_pairs,
+            default_timeout: self.default_timeout,
+            max_response_bytes: self.max_response_bytes,
+            request_compression: self.request_compression,
+            coalescer: self.request_coalescing.map(Coalescer::new),
+            idempotency_keys: self.idempotency_keys,
+            #[cfg(feature = "http-cache")]
+            http_cache: self.http_cache,
+        })
+    }
+}
+
+impl<T: HttpClientBuilder> BridgeBuilderInner<T> {
+    pub fn with_user_agent(self, user_agent: impl Into<String>) -> Self {
+        self.map_client(|client| client.user_agent(user_agent.into().as_str()))
+    }
+
+    pub fn with_redirect_policy(self, policy: RedirectPolicy) -> Self {
+        self.map_client(|client| client.redirect(policy.into()))
+    }
+
+    pub fn with_pool_max_idle_per_host(self, max: usize) -> Self {
+        self.map_client(|client| client.pool_max_idle_per_host(max))
     }
 
     /// Set an optional timeout for idle sockets being kept-alive.
@@ -86,58 +354,285 @@ impl BridgeBuilderInner<reqwest::ClientBuilder> {
     ///
     /// Default is 90 seconds.
     pub fn with_pool_idle_timeout(self, max: Option<Duration>) -> Self {
+        self.map_client(|client| client.pool_idle_timeout(max))
+    }
+
+    /// Sends the requests through the given proxy. Can be called multiple times, eg. to set a proxy for
+    /// HTTP and another one for HTTPS requests.
+    ///
+    /// If the proxy URL isn't supported the error is returned when building the bridge.
+    pub fn with_proxy(self, proxy: Proxy) -> Self {
+        match proxy.into_reqwest() {
+            Ok(proxy) => self.map_client(|client| client.proxy(proxy)),
+            Err(error) => self.with_error(error.into()),
+        }
+    }
+
+    /// Sends the requests directly, ignoring both the proxies previously set and the ones of the environment.
+    pub fn without_proxies(self) -> Self {
+        self.map_client(|client| client.no_proxy())
+    }
+
+    /// Sets the timeout of the connection phase, included in the timeout of the request.
+    ///
+    /// Default is no timeout.
+    pub fn with_connect_timeout(self, timeout: Duration) -> Self {
+        self.map_client(|client| client.connect_timeout(timeout))
+    }
+
+    /// Sets the interval of the TCP keepalive probes of the connections.
+    ///
+    /// Pass `None` to disable keepalive. Default is 15 seconds.
+    pub fn with_tcp_keepalive(self, interval: Option<Duration>) -> Self {
+        self.map_client(|client| client.tcp_keepalive(interval))
+    }
+
+    /// Only speaks HTTP/1 with the servers.
+    pub fn with_http1_only(self) -> Self {
+        self.map_client(|client| client.http1_only())
+    }
+
+    /// Speaks HTTP/2 with the servers, without negotiating it first. Only use it when every endpoint
+    /// supports HTTP/2.
+    pub fn with_http2_prior_knowledge(self) -> Self {
+        self.map_client(|client| client.http2_prior_knowledge())
+    }
+
+    /// Binds the connections to the given local address, eg. to pick the network interface of the requests.
+    pub fn with_local_address(self, address: IpAddr) -> Self {
+        self.map_client(|client| client.local_address(address))
+    }
+
+    /// Trusts the certificates of the given PEM bundle, eg. the one of a private CA, in addition to the
+    /// built-in root certificates.
+    ///
+    /// If the bundle can't be parsed the error is returned when building the bridge.
+    pub fn with_root_certificate(self, pem: impl AsRef<[u8]>) -> Self {
+        match tls::parse_certificates(pem.as_ref()) {
+            Ok(certificates) => self.map_client(|client| {
+                certificates
+                    .into_iter()
+                    .fold(client, reqwest::ClientBuilder::add_root_certificate)
+            }),
+            Err(error) => self.with_error(error),
+        }
+    }
+
+    /// Presents the given client certificate to the servers requiring mutual TLS.
+    ///
+    /// If the identity can't be parsed the error is returned when building the bridge.
+    pub fn with_identity(self, identity: TlsIdentity) -> Self {
+        match identity.configure() {
+            Ok(configure) => self.map_client(configure),
+            Err(error) => self.with_error(error),
+        }
+    }
+
+    /// Sets the minimum TLS version accepted when connecting to the server.
+    pub fn with_tls_min_version(self, version: TlsVersion) -> Self {
+        self.map_client(|client| client.tls_version_min(version.into()))
+    }
+
+    /// Accepts any certificate presented by the server, including expired and self-signed ones, for
+    /// any host name.
+    ///
+    /// # Warning
+    ///
+    /// This disables the authentication of the server and makes the connection vulnerable to
+    /// man-in-the-middle attacks. Only use it against local development servers.
+    pub fn danger_accept_invalid_certs(self, accept_invalid_certs: bool) -> Self {
+        self.map_client(|client| client.danger_accept_invalid_certs(accept_invalid_certs))
+    }
+
+    fn map_client(self, map: impl FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder) -> Self {
         Self {
-            inner: self.inner.pool_idle_timeout(max),
-            #[cfg(feature = "auth0")]
-            auth0: self.auth0,
+            inner: self.inner.map_http_client(map),
+            client_options: true,
+            ..self
         }
     }
+}
 
-    pub fn with_middleware(self, layer: impl Middleware) -> BridgeBuilderInner<reqwest_middleware::ClientBuilder> {
-        let client = self.inner.build().expect("Unable to create Bridge");
-        BridgeBuilderInner {
-            inner: reqwest_middleware::ClientBuilder::new(client).with(layer),
+impl BridgeBuilderInner<reqwest::ClientBuilder> {
+    pub(crate) fn create() -> Self {
+        Self {
+            inner: reqwest::ClientBuilder::new(),
             #[cfg(feature = "auth0")]
-            auth0: self.auth0,
+            auth0: None,
+            retry_policy: None,
+            circuit_breaker: None,
+            rate_limit: None,
+            concurrency_limit: None,
+            load_balancing: LoadBalancingPolicy::default(),
+            default_headers: HeaderMap::new(),
+            default_query_pairs: Vec::new(),
+            default_timeout: Duration::from_secs(60),
+            max_response_bytes: None,
+            request_compression: None,
+            request_coalescing: None,
+            idempotency_keys: None,
+            #[cfg(feature = "http-cache")]
+            http_cache: None,
+            client_options: false,
+            error: None,
+        }
+    }
+
+    /// Adds a middleware layer to the client used by the [Bridge].
+    ///
+    /// # Panics
+    ///
+    /// Panics if an option set earlier on this builder is invalid. The client is built, and can fail, only
+    /// when the bridge is. Use [Self::try_with_middleware] to handle the error.
+    pub fn with_middleware(self, layer: impl Middleware) -> MiddlewareBridgeBuilder {
+        self.try_with_middleware(layer).expect("Unable to create Bridge")
+    }
+
+    /// Adds a middleware layer to the client used by the [Bridge], failing if the builder has been misconfigured.
+    pub fn try_with_middleware(self, layer: impl Middleware) -> Result<MiddlewareBridgeBuilder, BridgeBuildError> {
+        let builder = self.map_inner(|client| MiddlewareClientBuilder {
+            client,
+            middlewares: vec![Arc::new(layer)],
+        });
+        match builder.error {
+            Some(error) => Err(error),
+            None => Ok(builder),
+        }
+    }
+
+    /// Uses the given client, eg. one shared by many bridges to share its connection pool, instead of
+    /// building a new one.
+    ///
+    /// The client can be either a [reqwest::Client] or a [reqwest_middleware::ClientWithMiddleware]. The client
+    /// options, like the user agent or the proxy, have to be set on the given client instead: setting them on
+    /// this builder beforehand is reported as [BridgeBuildError::InvalidConfig] when building the bridge.
+    pub fn with_client<C: BridgeClient>(self, client: C) -> BridgeBuilderInner<C> {
+        let client_options = self.client_options;
+        let builder = self.map_inner(|_| client);
+        match client_options {
+            true => builder.with_error(BridgeBuildError::InvalidConfig {
+                field: "client".to_string(),
+                reason: "the client options set on the builder would be ignored by the given client".to_string(),
+            }),
+            false => builder,
         }
     }
 
     /// Creates a [Bridge] from this builder.
     ///
     /// The given endpoint will be the base URL of all the requests made by the Bridge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bridge cannot be created. Use [Self::try_build] to handle the error.
     pub fn build(self, endpoint: Url) -> Bridge {
-        Bridge {
-            inner_client: self.inner.build().expect("Unable to create Bridge"),
-            endpoint,
-            #[cfg(feature = "auth0")]
-            auth0_opt: self.auth0,
-        }
+        self.try_build(endpoint).expect("Unable to create Bridge")
+    }
+
+    /// Creates a [Bridge] from this builder, failing if it has been misconfigured.
+    ///
+    /// The given endpoint will be the base URL of all the requests made by the Bridge.
+    pub fn try_build(self, endpoint: Url) -> Result<Bridge, BridgeBuildError> {
+        self.try_into_bridge(vec![endpoint], |inner| Ok(inner.build()?))
+    }
+
+    /// Creates a [Bridge] sending its requests to multiple endpoints, eg. the replicas of a service.
+    ///
+    /// The endpoint of every request is picked according to the policy set with
+    /// [Self::with_load_balancing]. For the failover strategy, endpoints are preferred in the given order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bridge cannot be created or no endpoint is given. Use [Self::try_build_with_endpoints]
+    /// to handle the error.
+    pub fn build_with_endpoints(self, endpoints: Vec<Url>) -> Bridge {
+        self.try_build_with_endpoints(endpoints)
+            .expect("Unable to create Bridge")
+    }
+
+    /// Creates a [Bridge] sending its requests to multiple endpoints, failing if it has been misconfigured.
+    pub fn try_build_with_endpoints(self, endpoints: Vec<Url>) -> Result<Bridge, BridgeBuildError> {
+        self.try_into_bridge(endpoints, |inner| Ok(inner.build()?))
     }
 }
 
-impl BridgeBuilderInner<reqwest_middleware::ClientBuilder> {
+impl BridgeBuilderInner<MiddlewareClientBuilder> {
     pub fn with(self, layer: impl Middleware) -> Self {
-        Self {
-            inner: self.inner.with(layer),
-            #[cfg(feature = "auth0")]
-            auth0: self.auth0,
-        }
+        self.with_arc(Arc::new(layer))
     }
 
     pub fn with_arc(self, layer: Arc<dyn Middleware>) -> Self {
-        Self {
-            inner: self.inner.with_arc(layer),
-            #[cfg(feature = "auth0")]
-            auth0: self.auth0,
-        }
+        let mut inner = self.inner;
+        inner.middlewares.push(layer);
+        Self { inner, ..self }
     }
 
+    /// Creates a bridge from this builder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bridge cannot be created. Use [Self::try_build] to handle the error.
     pub fn build(self, endpoint: Url) -> BridgeImpl<reqwest_middleware::ClientWithMiddleware> {
-        BridgeImpl {
-            inner_client: self.inner.build(),
-            endpoint,
-            #[cfg(feature = "auth0")]
-            auth0_opt: self.auth0,
-        }
+        self.try_build(endpoint).expect("Unable to create Bridge")
+    }
+
+    /// Creates a bridge from this builder, failing if it has been misconfigured.
+    pub fn try_build(
+        self,
+        endpoint: Url,
+    ) -> Result<BridgeImpl<reqwest_middleware::ClientWithMiddleware>, BridgeBuildError> {
+        self.try_into_bridge(vec![endpoint], MiddlewareClientBuilder::build)
+    }
+
+    /// Creates a bridge sending its requests to multiple endpoints, eg. the replicas of a service.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bridge cannot be created or no endpoint is given. Use [Self::try_build_with_endpoints]
+    /// to handle the error.
+    pub fn build_with_endpoints(self, endpoints: Vec<Url>) -> BridgeImpl<reqwest_middleware::ClientWithMiddleware> {
+        self.try_build_with_endpoints(endpoints)
+            .expect("Unable to create Bridge")
+    }
+
+    /// Creates a bridge sending its requests to multiple endpoints, failing if it has been misconfigured.
+    pub fn try_build_with_endpoints(
+        self,
+        endpoints: Vec<Url>,
+    ) -> Result<BridgeImpl<reqwest_middleware::ClientWithMiddleware>, BridgeBuildError> {
+        self.try_into_bridge(endpoints, MiddlewareClientBuilder::build)
+    }
+}
+
+impl<C: BridgeClient> BridgeBuilderInner<C> {
+    /// Creates a bridge using the given client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bridge cannot be created. Use [Self::try_build] to handle the error.
+    pub fn build(self, endpoint: Url) -> BridgeImpl<C> {
+        self.try_build(endpoint).expect("Unable to create Bridge")
+    }
+
+    /// Creates a bridge using the given client, failing if it has been misconfigured.
+    pub fn try_build(self, endpoint: Url) -> Result<BridgeImpl<C>, BridgeBuildError> {
+        self.try_into_bridge(vec![endpoint], Ok)
+    }
+
+    /// Creates a bridge using the given client, sending its requests to multiple endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bridge cannot be created or no endpoint is given. Use [Self::try_build_with_endpoints]
+    /// to handle the error.
+    pub fn build_with_endpoints(self, endpoints: Vec<Url>) -> BridgeImpl<C> {
+        self.try_build_with_endpoints(endpoints)
+            .expect("Unable to create Bridge")
+    }
+
+    /// Creates a bridge using the given client, sending its requests to multiple endpoints, failing if it has
+    /// been misconfigured.
+    pub fn try_build_with_endpoints(self, endpoints: Vec<Url>) -> Result<BridgeImpl<C>, BridgeBuildError> {
+        self.try_into_bridge(endpoints, Ok)
     }
 }
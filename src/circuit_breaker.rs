@@ -0,0 +1,401 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use reqwest::Url;
+
+use crate::errors::{PrimaBridgeError, PrimaBridgeResult};
+use crate::request::ReceivedResponse;
+
+/// The number of buckets the failure-rate window is split into.
+const WINDOW_BUCKETS: u32 = 10;
+
+/// The state of the circuit breaker of a bridge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally, and their outcome is recorded.
+    Closed,
+    /// Too many requests failed: requests are rejected without being sent until the cool-down is over.
+    Open,
+    /// The cool-down is over: a limited number of trial requests is let through to probe the server.
+    HalfOpen,
+}
+
+/// Configures the circuit breaker of a bridge.
+///
+/// The breaker records the outcome of every request sent in a sliding time window. When at least
+/// `minimum_requests` requests were sent in the window and the rate of failures reaches `failure_rate_threshold`
+/// the circuit opens, and every request fails immediately with [PrimaBridgeError::CircuitOpen].
+///
+/// After `cool_down` the circuit becomes half-open and lets `half_open_requests` trial requests through:
+/// if all of them succeed the circuit closes again, otherwise it opens for another cool-down.
+///
+/// Transport errors and `5xx` responses count as failures.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerPolicy {
+    failure_rate_threshold: f64,
+    minimum_requests: u32,
+    window: Duration,
+    cool_down: Duration,
+    half_open_requests: u32,
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            minimum_requests: 20,
+            window: Duration::from_secs(60),
+            cool_down: Duration::from_secs(30),
+            half_open_requests: 3,
+        }
+    }
+}
+
+impl CircuitBreakerPolicy {
+    /// Sets the rate of failed requests (between 0 and 1) that opens the circuit. Defaults to 0.5.
+    pub fn with_failure_rate_threshold(self, failure_rate_threshold: f64) -> Self {
+        Self {
+            failure_rate_threshold: failure_rate_threshold.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Sets the number of requests that must be recorded in the window before the failure rate is evaluated.
+    ///
+    /// Defaults to 20.
+    pub fn with_minimum_requests(self, minimum_requests: u32) -> Self {
+        Self {
+            minimum_requests: minimum_requests.max(1),
+            ..self
+        }
+    }
+
+    /// Sets the duration of the sliding window in which outcomes are recorded. Defaults to 60 seconds.
+    pub fn with_window(self, window: Duration) -> Self {
+        Self { window, ..self }
+    }
+
+    /// Sets how long the circuit stays open before letting trial requests through. Defaults to 30 seconds.
+    pub fn with_cool_down(self, cool_down: Duration) -> Self {
+        Self { cool_down, ..self }
+    }
+
+    /// Sets how many trial requests must succeed while half-open to close the circuit. Defaults to 3.
+    pub fn with_half_open_requests(self, half_open_requests: u32) -> Self {
+        Self {
+            half_open_requests: half_open_requests.max(1),
+            ..self
+        }
+    }
+}
+
+/// A circuit breaker, shared by all the clones of the bridge it has been created for.
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBreaker {
+    policy: Arc<CircuitBreakerPolicy>,
+    state: Arc<Mutex<BreakerState>>,
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    status: Status,
+    buckets: VecDeque<Bucket>,
+    /// The number of times the circuit went half-open, telling the trial requests of a cycle from the ones
+    /// of the previous cycles.
+    half_open_cycles: u64,
+}
+
+#[derive(Debug)]
+enum Status {
+    Closed,
+    Open { until: Instant },
+    HalfOpen { cycle: u64, in_flight: u32, successes: u32 },
+}
+
+#[derive(Debug)]
+struct Bucket {
+    started_at: Instant,
+    successes: u32,
+    failures: u32,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(policy: CircuitBreakerPolicy) -> Self {
+        Self {
+            policy: Arc::new(policy),
+            state: Arc::new(Mutex::new(BreakerState {
+                status: Status::Closed,
+                buckets: VecDeque::new(),
+                half_open_cycles: 0,
+            })),
+        }
+    }
+
+    /// Creates a breaker with the same policy, which doesn't share the state with this one.
+    pub(crate) fn detached(&self) -> Self {
+        Self::new(self.policy.as_ref().clone())
+    }
+
+    pub(crate) fn state(&self) -> CircuitState {
+        match self.lock().status {
+            Status::Closed => CircuitState::Closed,
+            Status::Open { until } if Instant::now() < until => CircuitState::Open,
+            Status::Open { .. } | Status::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Asks the breaker for the permission to send a request to `url`.
+    pub(crate) fn acquire(&self, url: &Url) -> PrimaBridgeResult<CircuitPermit> {
+        let mut state = self.lock();
+
+        if let Status::Open { until } = state.status {
+            if Instant::now() < until {
+                return Err(PrimaBridgeError::CircuitOpen(url.clone()));
+            }
+            state.half_open_cycles += 1;
+            state.status = Status::HalfOpen {
+                cycle: state.half_open_cycles,
+                in_flight: 0,
+                successes: 0,
+            };
+        }
+
+        let trial = match &mut state.status {
+            Status::HalfOpen {
+                cycle,
+                in_flight,
+                successes,
+            } => {
+                if *in_flight + *successes >= self.policy.half_open_requests {
+                    return Err(PrimaBridgeError::CircuitOpen(url.clone()));
+                }
+                *in_flight += 1;
+                Some(*cycle)
+            }
+            _ => None,
+        };
+
+        Ok(CircuitPermit {
+            breaker: self.clone(),
+            trial,
+            success: None,
+        })
+    }
+
+    fn release(&self, trial: Option<u64>, success: Option<bool>) {
+        let mut state = self.lock();
+        let now = Instant::now();
+
+        match (&mut state.status, trial) {
+            // The trial requests of a previous cycle are not relevant anymore
+            (
+                Status::HalfOpen {
+                    cycle,
+                    in_flight,
+                    successes,
+                },
+                Some(trial_cycle),
+            ) if *cycle == trial_cycle => {
+                *in_flight -= 1;
+                match success {
+                    Some(true) => {
+                        *successes += 1;
+                        if *successes >= self.policy.half_open_requests {
+                            state.status = Status::Closed;
+                            state.buckets.clear();
+                        }
+                    }
+                    Some(false) => self.open(&mut state, now),
+                    None => (),
+                }
+            }
+            (Status::Closed, None) => {
+                let Some(success) = success else {
+                    return;
+                };
+                self.record(&mut state, now, success);
+                if self.failure_rate_exceeded(&state) {
+                    self.open(&mut state, now);
+                }
+            }
+            // The circuit changed state while the request was in flight: its outcome is not relevant anymore.
+            _ => (),
+        }
+    }
+
+    fn record(&self, state: &mut BreakerState, now: Instant, success: bool) {
+        let bucket_duration = self.policy.window / WINDOW_BUCKETS;
+        while state
+            .buckets
+            .front()
+            .is_some_and(|bucket| now.duration_since(bucket.started_at) >= self.policy.window)
+        {
+            state.buckets.pop_front();
+        }
+
+        let needs_bucket = state
+            .buckets
+            .back()
+            .is_none_or(|bucket| now.duration_since(bucket.started_at) >= bucket_duration);
+        if needs_bucket {
+            state.buckets.push_back(Bucket {
+                started_at: now,
+                successes: 0,
+                failures: 0,
+            });
+        }
+
+        if let Some(bucket) = state.buckets.back_mut() {
+            if success {
+                bucket.successes += 1;
+            } else {
+                bucket.failures += 1;
+            }
+        }
+    }
+
+    fn failure_rate_exceeded(&self, state: &BreakerState) -> bool {
+        let (successes, failures) = state.buckets.iter().fold((0, 0), |(successes, failures), bucket| {
+            (successes + bucket.successes, failures + bucket.failures)
+        });
+        let total = successes + failures;
+
+        total >= self.policy.minimum_requests && failures as f64 / total as f64 >= self.policy.failure_rate_threshold
+    }
+
+    fn open(&self, state: &mut BreakerState, now: Instant) {
+        state.status = Status::Open {
+            until: now + self.policy.cool_down,
+        };
+        state.buckets.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BreakerState> {
+        // The state is always left consistent, so it's fine to keep using it after a panic
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The permission to send a request, granted by a [CircuitBreaker].
+///
+/// A permit dropped without recording an outcome (eg. because the request future got cancelled) is released
+/// without affecting the failure rate.
+pub(crate) struct CircuitPermit {
+    breaker: CircuitBreaker,
+    /// The half-open cycle the permit has been granted in, if it's a trial request.
+    trial: Option<u64>,
+    success: Option<bool>,
+}
+
+impl CircuitPermit {
+    pub(crate) fn record<R: ReceivedResponse>(mut self, result: &PrimaBridgeResult<R>) {
+        let failed = match result {
+            Ok(response) => response.status().is_server_error(),
+            Err(PrimaBridgeError::WrongStatusCode(_, status_code)) => status_code.is_server_error(),
+            Err(PrimaBridgeError::HttpError { .. } | PrimaBridgeError::MiddlewareError(_)) => true,
+            Err(_) => false,
+        };
+        self.success = Some(!failed);
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        self.breaker.release(self.trial, self.success);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::Response;
+
+    fn url() -> Url {
+        Url::parse("http://prima.it").unwrap()
+    }
+
+    fn failure() -> PrimaBridgeResult<Response> {
+        Err(PrimaBridgeError::WrongStatusCode(
+            url(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))
+    }
+
+    fn not_found() -> PrimaBridgeResult<Response> {
+        Err(PrimaBridgeError::WrongStatusCode(url(), StatusCode::NOT_FOUND))
+    }
+
+    #[test]
+    fn opens_when_failure_rate_is_reached() {
+        let breaker = CircuitBreaker::new(
+            CircuitBreakerPolicy::default()
+                .with_minimum_requests(4)
+                .with_failure_rate_threshold(0.5),
+        );
+
+        breaker.acquire(&url()).unwrap().record(&not_found());
+        breaker.acquire(&url()).unwrap().record(&failure());
+        breaker.acquire(&url()).unwrap().record(&not_found());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.acquire(&url()).unwrap().record(&failure());
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(breaker.acquire(&url()), Err(PrimaBridgeError::CircuitOpen(_))));
+    }
+
+    #[test]
+    fn cancelled_requests_are_not_recorded() {
+        let breaker = CircuitBreaker::new(CircuitBreakerPolicy::default().with_minimum_requests(1));
+
+        drop(breaker.acquire(&url()).unwrap());
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn half_open_limits_trial_requests_and_reopens_on_failure() {
+        let breaker = CircuitBreaker::new(
+            CircuitBreakerPolicy::default()
+                .with_minimum_requests(1)
+                .with_cool_down(Duration::ZERO)
+                .with_half_open_requests(1),
+        );
+
+        breaker.acquire(&url()).unwrap().record(&failure());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        let trial = breaker.acquire(&url()).unwrap();
+        assert!(breaker.acquire(&url()).is_err());
+
+        trial.record(&failure());
+        let trial = breaker.acquire(&url()).unwrap();
+        trial.record(&not_found());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn ignores_the_trial_requests_of_a_previous_half_open_cycle() {
+        let breaker = CircuitBreaker::new(
+            CircuitBreakerPolicy::default()
+                .with_minimum_requests(1)
+                .with_cool_down(Duration::ZERO)
+                .with_half_open_requests(2),
+        );
+        breaker.acquire(&url()).unwrap().record(&failure());
+
+        let failing = breaker.acquire(&url()).unwrap();
+        let previous_cycle = breaker.acquire(&url()).unwrap();
+        failing.record(&failure());
+        let trial = breaker.acquire(&url()).unwrap();
+        previous_cycle.record(&not_found());
+        drop(breaker.acquire(&url()).unwrap());
+
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        trial.record(&not_found());
+        breaker.acquire(&url()).unwrap().record(&not_found());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION, COOKIE, IF_MODIFIED_SINCE, IF_NONE_MATCH};
+use reqwest::{Method, Url};
+use tokio::sync::watch;
+use tracing::Instrument;
+
+use crate::errors::{PrimaBridgeError, PrimaBridgeResult};
+use crate::request::client_span;
+use crate::{DeliverableRequest, Response};
+
+/// Coalesces the identical `GET` requests made at the same time by a [Bridge](crate::Bridge), so that they
+/// share a single call to the server, eg. on a cold cache during a traffic spike.
+///
+/// Requests are identical when they have the same final URL and the same values of the selected headers, as
+/// well as the same credentials (`Authorization` and `Cookie` headers, including the auth0 token), conditional
+/// headers, `ignore_status_code` and maximum response size. Every coalesced
+/// request gets its own copy of the [Response], while its span gets the `prima_bridge.coalesced` attribute.
+///
+/// The first request is sent with its own timeout and retry policy, which also apply to the ones coalesced
+/// with it. When it fails, they fail with the same
+/// [WrongStatusCode](PrimaBridgeError::WrongStatusCode) or
+/// [ResponseTooLarge](PrimaBridgeError::ResponseTooLarge) error, or with a
+/// [CoalescedRequestFailed](PrimaBridgeError::CoalescedRequestFailed) error describing it otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct RequestCoalescing {
+    headers: Vec<HeaderName>,
+}
+
+impl RequestCoalescing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only coalesces the requests with the same value of the given header, eg. `Accept-Language` or
+    /// `Authorization` when it's set on the requests.
+    pub fn with_header(self, header: HeaderName) -> Self {
+        let mut headers = self.headers;
+        headers.push(header);
+        Self { headers }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RequestKey {
+    url: Url,
+    headers: Vec<Option<HeaderValue>>,
+    ignore_status_code: bool,
+    max_response_bytes: Option<u64>,
+}
+
+type InFlight = HashMap<RequestKey, watch::Receiver<Option<PrimaBridgeResult<Response>>>>;
+
+/// Whether a request is the first of the identical ones in flight, or waits for the response to it.
+enum Role {
+    Lead(watch::Sender<Option<PrimaBridgeResult<Response>>>),
+    Follow(watch::Receiver<Option<PrimaBridgeResult<Response>>>),
+}
+
+/// The requests in flight, shared by all the clones of a bridge.
+#[derive(Debug, Clone)]
+pub(crate) struct Coalescer {
+    headers: Vec<HeaderName>,
+    in_flight: Arc<Mutex<InFlight>>,
+}
+
+impl Coalescer {
+    pub(crate) fn new(coalescing: RequestCoalescing) -> Self {
+        let mut headers = coalescing.headers;
+        // Requests made on behalf of different users never share a response
+        headers.extend([AUTHORIZATION, COOKIE, IF_NONE_MATCH, IF_MODIFIED_SINCE]);
+        Self {
+            headers,
+            in_flight: Default::default(),
+        }
+    }
+
+    fn key<'a, R: DeliverableRequest<'a>>(&self, request: &R) -> RequestKey {
+        let mut request_headers = request.get_bridge().default_headers.clone();
+        request_headers.extend(request.get_custom_headers().clone());
+        #[cfg(feature = "auth0")]
+        request_headers.extend(request.get_auth0_headers());
+        RequestKey {
+            url: request.get_url(),
+            headers: self
+                .headers
+                .iter()
+                .map(|name| request_headers.get(name).cloned())
+                .collect(),
+            ignore_status_code: request.get_ignore_status_code(),
+            max_response_bytes: request
+                .get_max_response_bytes()
+                .or(request.get_bridge().max_response_bytes),
+        }
+    }
+
+    /// Sends a `GET` request, or waits for the response to an identical one already in flight.
+    pub(crate) async fn send<'a, R: DeliverableRequest<'a> + Send>(&self, request: R) -> PrimaBridgeResult<Response> {
+        let key = self.key(&request);
+        let role = {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(PoisonError::into_inner);
+            match in_flight.get(&key) {
+                Some(receiver) => Role::Follow(receiver.clone()),
+                None => {
+                    let (sender, receiver) = watch::channel(None);
+                    in_flight.insert(key.clone(), receiver);
+                    Role::Lead(sender)
+                }
+            }
+        };
+        let mut receiver = match role {
+            Role::Follow(receiver) => receiver,
+            Role::Lead(sender) => return self.lead(key, sender, request).await,
+        };
+
+        let span = client_span(&Method::GET, request.get_route(), &key.url, request.get_id());
+        span.record("prima_bridge.coalesced", true);
+        let shared = async {
+            let result = receiver.wait_for(Option::is_some).await.ok()?;
+            result.as_ref().map(|result| share(&key.url, result))
+        }
+        .instrument(span)
+        .await;
+
+        match shared {
+            Some(result) => result,
+            // The first request has been dropped before receiving its response
+            None => request.send_as().await,
+        }
+    }
+
+    /// Sends the first of the identical requests, sharing its response with the ones coalesced meanwhile.
+    async fn lead<'a, R: DeliverableRequest<'a> + Send>(
+        &self,
+        key: RequestKey,
+        sender: watch::Sender<Option<PrimaBridgeResult<Response>>>,
+        request: R,
+    ) -> PrimaBridgeResult<Response> {
+        let guard = InFlightGuard {
+            in_flight: &self.in_flight,
+            key,
+        };
+
+        let result = request.send_as().await;
+
+        let shared = share(&guard.key.url, &result);
+        // Later requests won't be coalesced with this one anymore
+        drop(guard);
+        let _ = sender.send(Some(shared));
+        result
+    }
+}
+
+/// Stops coalescing requests with the one in flight once it completes, or when it gets dropped.
+struct InFlightGuard<'c> {
+    in_flight: &'c Mutex<InFlight>,
+    key: RequestKey,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.key);
+    }
+}
+
+/// Returns a copy of the result of a request for the ones coalesced with it.
+fn share(url: &Url, result: &PrimaBridgeResult<Response>) -> PrimaBridgeResult<Response> {
+    match result {
+        Ok(response) => Ok(response.duplicate()),
+        Err(PrimaBridgeError::WrongStatusCode(url, status_code)) => {
+            Err(PrimaBridgeError::WrongStatusCode(url.clone(), *status_code))
+        }
+        Err(PrimaBridgeError::ResponseTooLarge { url, limit }) => Err(PrimaBridgeError::ResponseTooLarge {
+            url: url.clone(),
+            limit: *limit,
+        }),
+        Err(PrimaBridgeError::CoalescedRequestFailed { url, reason }) => {
+            Err(PrimaBridgeError::CoalescedRequestFailed {
+                url: url.clone(),
+                reason: reason.clone(),
+            })
+        }
+        Err(error) => Err(PrimaBridgeError::CoalescedRequestFailed {
+            url: url.clone(),
+            reason: error.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,256 @@
+//! Encoding of request bodies and decoding of response bodies in formats other than plain JSON.
+
+use std::error::Error;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::errors::{BodyStructure, PrimaBridgeError, PrimaBridgeResult};
+
+/// A format request and response bodies are encoded in, used with
+/// [DeliverableRequest::encoded_body](crate::DeliverableRequest::encoded_body) and
+/// [Response::decode](crate::Response::decode).
+///
+/// The values a codec can encode and decode implement [Encode] and [Decode] for it.
+pub trait Codec {
+    /// The media type of the encoded bodies, sent in the `Content-Type` and `Accept` headers.
+    const CONTENT_TYPE: &'static str;
+}
+
+/// A value which can be encoded with the codec `C`.
+pub trait Encode<C: Codec> {
+    fn encode(&self) -> PrimaBridgeResult<Vec<u8>>;
+}
+
+/// A value which can be decoded with the codec `C`.
+pub trait Decode<C: Codec>: Sized {
+    fn decode(bytes: &[u8]) -> PrimaBridgeResult<Self>;
+}
+
+/// JSON, with [serde_json].
+#[derive(Debug, Clone, Copy)]
+pub struct Json;
+
+impl Codec for Json {
+    const CONTENT_TYPE: &'static str = "application/json";
+}
+
+impl<T: Serialize + ?Sized> Encode<Json> for T {
+    fn encode(&self) -> PrimaBridgeResult<Vec<u8>> {
+        serde_json::to_vec(self).map_err(encode_error::<Json>)
+    }
+}
+
+impl<T: DeserializeOwned> Decode<Json> for T {
+    fn decode(bytes: &[u8]) -> PrimaBridgeResult<Self> {
+        let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+        serde_path_to_error::deserialize(&mut deserializer)
+            .map_err(|error| decode_error::<Json, _>(error, || serde_json::from_slice(bytes).ok()))
+    }
+}
+
+/// MessagePack, with [rmp_serde]. Structs are encoded as maps, so that their fields are named.
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy)]
+pub struct MessagePack;
+
+#[cfg(feature = "msgpack")]
+impl Codec for MessagePack {
+    const CONTENT_TYPE: &'static str = "application/msgpack";
+}
+
+#[cfg(feature = "msgpack")]
+impl<T: Serialize + ?Sized> Encode<MessagePack> for T {
+    fn encode(&self) -> PrimaBridgeResult<Vec<u8>> {
+        rmp_serde::to_vec_named(self).map_err(encode_error::<MessagePack>)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T: DeserializeOwned> Decode<MessagePack> for T {
+    fn decode(bytes: &[u8]) -> PrimaBridgeResult<Self> {
+        let mut deserializer = rmp_serde::Deserializer::new(bytes);
+        serde_path_to_error::deserialize(&mut deserializer)
+            .map_err(|error| decode_error::<MessagePack, _>(error, || rmp_serde::from_slice(bytes).ok()))
+    }
+}
+
+/// CBOR, with [ciborium].
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone, Copy)]
+pub struct Cbor;
+
+#[cfg(feature = "cbor")]
+impl Codec for Cbor {
+    const CONTENT_TYPE: &'static str = "application/cbor";
+}
+
+#[cfg(feature = "cbor")]
+impl<T: Serialize + ?Sized> Encode<Cbor> for T {
+    fn encode(&self) -> PrimaBridgeResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes).map_err(encode_error::<Cbor>)?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<T: DeserializeOwned> Decode<Cbor> for T {
+    fn decode(bytes: &[u8]) -> PrimaBridgeResult<Self> {
+        ciborium::from_reader(bytes).map_err(|error| {
+            // The deserializer of ciborium is private, so the decoding is replayed on a generic value to find
+            // the failing path
+            let value: Option<Value> = ciborium::from_reader(bytes).ok();
+            match value.map(|value| (serde_path_to_error::deserialize::<_, T>(&value), value)) {
+                Some((Err(path_error), value)) => decode_error::<Cbor, _>(path_error, || Some(value)),
+                _ => PrimaBridgeError::DecodeError {
+                    content_type: Cbor::CONTENT_TYPE,
+                    path: None,
+                    body_structure: None,
+                    source: Box::new(error),
+                },
+            }
+        })
+    }
+}
+
+/// XML, with [quick_xml]. The root element of a struct is named after its type.
+#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+#[cfg(feature = "xml")]
+#[derive(Debug, Clone, Copy)]
+pub struct Xml;
+
+#[cfg(feature = "xml")]
+impl Codec for Xml {
+    const CONTENT_TYPE: &'static str = "application/xml";
+}
+
+#[cfg(feature = "xml")]
+impl<T: Serialize + ?Sized> Encode<Xml> for T {
+    fn encode(&self) -> PrimaBridgeResult<Vec<u8>> {
+        quick_xml::se::to_string(self)
+            .map(String::into_bytes)
+            .map_err(encode_error::<Xml>)
+    }
+}
+
+#[cfg(feature = "xml")]
+impl<T: DeserializeOwned> Decode<Xml> for T {
+    fn decode(bytes: &[u8]) -> PrimaBridgeResult<Self> {
+        let xml = std::str::from_utf8(bytes).map_err(|error| PrimaBridgeError::DecodeError {
+            content_type: Xml::CONTENT_TYPE,
+            path: None,
+            body_structure: None,
+            source: Box::new(error),
+        })?;
+        let mut deserializer = quick_xml::de::Deserializer::from_str(xml);
+        serde_path_to_error::deserialize(&mut deserializer)
+            .map_err(|error| decode_error::<Xml, _>(error, || quick_xml::de::from_str(xml).ok()))
+    }
+}
+
+/// Protocol Buffers, with [prost]. Only the types generated by prost can be encoded and decoded.
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+#[cfg(feature = "protobuf")]
+#[derive(Debug, Clone, Copy)]
+pub struct Protobuf;
+
+#[cfg(feature = "protobuf")]
+impl Codec for Protobuf {
+    const CONTENT_TYPE: &'static str = "application/x-protobuf";
+}
+
+#[cfg(feature = "protobuf")]
+impl<T: prost::Message> Encode<Protobuf> for T {
+    fn encode(&self) -> PrimaBridgeResult<Vec<u8>> {
+        Ok(self.encode_to_vec())
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl<T: prost::Message + Default> Decode<Protobuf> for T {
+    fn decode(bytes: &[u8]) -> PrimaBridgeResult<Self> {
+        T::decode(bytes).map_err(|error| PrimaBridgeError::DecodeError {
+            content_type: Protobuf::CONTENT_TYPE,
+            path: None,
+            body_structure: None,
+            source: Box::new(error),
+        })
+    }
+}
+
+fn encode_error<C: Codec>(error: impl Error + Send + Sync + 'static) -> PrimaBridgeError {
+    PrimaBridgeError::EncodeError {
+        content_type: C::CONTENT_TYPE,
+        source: Box::new(error),
+    }
+}
+
+/// Creates the error of a self-describing format, with the shape of the body at the failing path if the body
+/// can be decoded as a generic value.
+fn decode_error<C, E>(error: serde_path_to_error::Error<E>, value: impl FnOnce() -> Option<Value>) -> PrimaBridgeError
+where
+    C: Codec,
+    E: Error + Send + Sync + 'static,
+{
+    let path = error.path().clone();
+    PrimaBridgeError::DecodeError {
+        content_type: C::CONTENT_TYPE,
+        body_structure: value().map(|value| BodyStructure::at_path(&value, &path)),
+        path: Some(path.to_string()),
+        source: Box::new(error.into_inner()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    fn assert_decode_error_at_age(result: PrimaBridgeResult<Person>) {
+        match result {
+            Err(PrimaBridgeError::DecodeError {
+                path, body_structure, ..
+            }) => {
+                assert_eq!(path.as_deref(), Some("age"));
+                assert_eq!(body_structure.unwrap().to_string(), "string");
+            }
+            other => panic!("expected DecodeError, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn json_decode_errors_carry_the_path_and_the_body_structure() {
+        let bytes = br#"{"name": "John", "age": "forty-two"}"#;
+
+        assert_decode_error_at_age(<Person as Decode<Json>>::decode(bytes));
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_decode_errors_carry_the_path_and_the_body_structure() {
+        let bytes = rmp_serde::to_vec_named(&serde_json::json!({"name": "John", "age": "forty-two"})).unwrap();
+
+        assert_decode_error_at_age(<Person as Decode<MessagePack>>::decode(&bytes));
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn cbor_decode_errors_carry_the_path_and_the_body_structure() {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&serde_json::json!({"name": "John", "age": "forty-two"}), &mut bytes).unwrap();
+
+        assert_decode_error_at_age(<Person as Decode<Cbor>>::decode(&bytes));
+    }
+}
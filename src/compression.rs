@@ -0,0 +1,89 @@
+use reqwest::header::HeaderValue;
+use serde::Deserialize;
+
+/// The size, in bytes, of the smallest body compressed by default.
+pub(crate) const DEFAULT_MIN_SIZE: usize = 1024;
+
+/// The encodings the request bodies can be compressed with, each one enabled by the cargo feature with the
+/// same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentEncoding {
+    #[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+/// The compression of the request bodies sent by a bridge, which sets their `Content-Encoding` header.
+///
+/// Only the bodies held in memory and at least `min_size` bytes long are compressed, while streams and
+/// multipart forms are sent as they are. So are the requests setting their own `Content-Encoding`, eg.
+/// `identity` to opt out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestCompression {
+    pub(crate) encoding: ContentEncoding,
+    pub(crate) min_size: usize,
+}
+
+impl RequestCompression {
+    /// Compresses the bodies of at least 1 KiB with the given encoding.
+    pub fn new(encoding: ContentEncoding) -> Self {
+        Self {
+            encoding,
+            min_size: DEFAULT_MIN_SIZE,
+        }
+    }
+
+    /// Compresses the bodies of at least 1 KiB with gzip.
+    #[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
+    #[cfg(feature = "gzip")]
+    pub fn gzip() -> Self {
+        Self::new(ContentEncoding::Gzip)
+    }
+
+    /// Compresses the bodies of at least 1 KiB with zstd.
+    #[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+    #[cfg(feature = "zstd")]
+    pub fn zstd() -> Self {
+        Self::new(ContentEncoding::Zstd)
+    }
+
+    /// Sets the size, in bytes, below which the bodies are sent uncompressed, as compressing them wouldn't
+    /// be worth it.
+    pub fn with_min_size(self, min_size: usize) -> Self {
+        Self { min_size, ..self }
+    }
+
+    pub(crate) fn applies_to(&self, body: &[u8]) -> bool {
+        body.len() >= self.min_size
+    }
+
+    pub(crate) fn content_encoding(&self) -> HeaderValue {
+        match self.encoding {
+            #[cfg(feature = "gzip")]
+            ContentEncoding::Gzip => HeaderValue::from_static("gzip"),
+            #[cfg(feature = "zstd")]
+            ContentEncoding::Zstd => HeaderValue::from_static("zstd"),
+        }
+    }
+
+    // Without any encoding enabled there's nothing to compress the body with
+    #[cfg_attr(not(any(feature = "gzip", feature = "zstd")), allow(unused_variables))]
+    pub(crate) fn compress(&self, body: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self.encoding {
+            #[cfg(feature = "gzip")]
+            ContentEncoding::Gzip => {
+                use std::io::Write;
+
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "zstd")]
+            ContentEncoding::Zstd => zstd::encode_all(body, zstd::DEFAULT_COMPRESSION_LEVEL),
+        }
+    }
+}
@@ -0,0 +1,783 @@
+//! Bridge configuration, deserializable from configuration files and environment variables.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Method, StatusCode, Url};
+use serde::Deserialize;
+
+#[cfg(feature = "auth0")]
+use crate::auth0;
+use crate::errors::BridgeBuildError;
+use crate::{compression, tls};
+use crate::{
+    Bridge, CircuitBreakerPolicy, ConcurrencyLimit, ContentEncoding, IdempotencyKeys, LoadBalancingPolicy,
+    LoadBalancingStrategy, Proxy, ProxyScope, RateLimit, RedirectPolicy, RequestCoalescing, RequestCompression,
+    RetryPolicy, RetryableError, TlsIdentity, TlsVersion,
+};
+
+/// The configuration of a [Bridge], covering every option of the [BridgeBuilder](crate::BridgeBuilder).
+///
+/// Durations are expressed as human readable strings, eg. `"500ms"`, `"30s"` or `"1m 30s"`.
+///
+/// ```toml
+/// endpoint = "https://api.prima.it"
+/// user_agent = "my-service"
+/// redirect_policy = { limited = 5 }
+/// default_timeout = "10s"
+///
+/// [default_headers]
+/// x-tenant = "prima"
+///
+/// [retry]
+/// max_attempts = 5
+/// initial_backoff = "200ms"
+/// ```
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BridgeConfig {
+    /// The base URL of all the requests made by the bridge.
+    pub endpoint: String,
+    /// Further base URLs, eg. the replicas of the service. Requests are balanced across them and `endpoint`
+    /// according to `load_balancing`.
+    #[serde(default)]
+    pub endpoints: Vec<String>,
+    pub load_balancing: Option<LoadBalancingConfig>,
+    pub user_agent: Option<String>,
+    pub redirect_policy: Option<RedirectPolicy>,
+    pub pool_max_idle_per_host: Option<usize>,
+    /// The timeout for idle sockets being kept-alive. Default is 90 seconds.
+    #[serde(default, with = "humantime_serde")]
+    pub pool_idle_timeout: Option<Duration>,
+    /// The timeout of the requests that don't set their own. Default is 60 seconds.
+    #[serde(default, with = "humantime_serde")]
+    pub default_timeout: Option<Duration>,
+    /// The maximum size of the response bodies, in bytes. Default is no limit.
+    pub max_response_bytes: Option<u64>,
+    pub request_compression: Option<RequestCompressionConfig>,
+    #[serde(default)]
+    pub default_headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub default_query_pairs: BTreeMap<String, String>,
+    pub proxy: Option<ProxyConfig>,
+    /// Whether to use the proxies set by the `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY`
+    /// environment variables when `proxy` is unset. Default is true.
+    #[serde(default = "default_environment_proxies")]
+    pub environment_proxies: bool,
+    /// The timeout of the connection phase. Default is no timeout.
+    #[serde(default, with = "humantime_serde")]
+    pub connect_timeout: Option<Duration>,
+    /// The interval of the TCP keepalive probes. Default is 15 seconds.
+    #[serde(default, with = "humantime_serde")]
+    pub tcp_keepalive: Option<Duration>,
+    #[serde(default)]
+    pub http1_only: bool,
+    #[serde(default)]
+    pub http2_prior_knowledge: bool,
+    pub local_address: Option<IpAddr>,
+    pub tls: Option<TlsConfig>,
+    pub retry: Option<RetryConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub concurrency_limit: Option<ConcurrencyLimitConfig>,
+    pub request_coalescing: Option<RequestCoalescingConfig>,
+    pub idempotency_keys: Option<IdempotencyKeysConfig>,
+    #[cfg_attr(docsrs, doc(cfg(feature = "http-cache")))]
+    #[cfg(feature = "http-cache")]
+    pub http_cache: Option<HttpCacheConfig>,
+    #[cfg_attr(docsrs, doc(cfg(feature = "auth0")))]
+    #[cfg(feature = "auth0")]
+    pub auth0: Option<Auth0Config>,
+}
+
+/// The outbound [Proxy] of a [BridgeConfig].
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyConfig {
+    pub url: String,
+    /// The requests sent through the proxy. Default is all of them.
+    #[serde(default)]
+    pub scope: ProxyScope,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// The hosts reached directly, in the format of the `NO_PROXY` environment variable.
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+}
+
+/// The [RequestCompression] of a [BridgeConfig].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RequestCompressionConfig {
+    pub encoding: ContentEncoding,
+    /// The size, in bytes, of the smallest body compressed. Default is 1 KiB.
+    pub min_size: Option<usize>,
+}
+
+/// The TLS settings of a [BridgeConfig]. Certificates and keys are read from files.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsConfig {
+    /// PEM bundles of certificates to trust in addition to the built-in root certificates.
+    pub root_certificates: Vec<PathBuf>,
+    pub identity: Option<TlsIdentityConfig>,
+    pub min_version: Option<TlsVersion>,
+    /// Accepts any certificate presented by the server. Only meant for local development.
+    pub danger_accept_invalid_certs: bool,
+}
+
+/// The client certificate of a [TlsConfig], selected by the `format` field.
+#[derive(Clone, Deserialize)]
+#[serde(tag = "format", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TlsIdentityConfig {
+    /// A PEM file holding the certificate chain and the private key.
+    Pem { path: PathBuf },
+    /// A PKCS#12 archive, and its password.
+    #[cfg_attr(docsrs, doc(cfg(feature = "native-tls")))]
+    #[cfg(feature = "native-tls")]
+    Pkcs12 { path: PathBuf, password: String },
+}
+
+// The passwords are redacted, so that they don't end up in the logs
+impl std::fmt::Debug for ProxyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxyConfig")
+            .field("url", &self.url)
+            .field("scope", &self.scope)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("no_proxy", &self.no_proxy)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for TlsIdentityConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pem { path } => f.debug_struct("Pem").field("path", path).finish(),
+            #[cfg(feature = "native-tls")]
+            Self::Pkcs12 { path, .. } => f
+                .debug_struct("Pkcs12")
+                .field("path", path)
+                .field("password", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+/// The configuration of a [RetryPolicy]. Unset fields keep the default of the policy.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryConfig {
+    pub max_attempts: Option<u32>,
+    #[serde(with = "humantime_serde")]
+    pub initial_backoff: Option<Duration>,
+    #[serde(with = "humantime_serde")]
+    pub max_backoff: Option<Duration>,
+    pub backoff_multiplier: Option<f64>,
+    pub jitter: Option<bool>,
+    pub retryable_status_codes: Option<Vec<u16>>,
+    pub retryable_errors: Option<Vec<RetryableError>>,
+    pub idempotent_only: Option<bool>,
+}
+
+/// The configuration of a [CircuitBreakerPolicy]. Unset fields keep the default of the policy.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CircuitBreakerConfig {
+    pub failure_rate_threshold: Option<f64>,
+    pub minimum_requests: Option<u32>,
+    #[serde(with = "humantime_serde")]
+    pub window: Option<Duration>,
+    #[serde(with = "humantime_serde")]
+    pub cool_down: Option<Duration>,
+    pub half_open_requests: Option<u32>,
+}
+
+/// The configuration of a [RateLimit].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    pub requests: u32,
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+    /// How long a request can wait to be sent. Requests wait as long as needed when unset.
+    #[serde(default, with = "humantime_serde")]
+    pub max_wait: Option<Duration>,
+}
+
+/// The configuration of a [ConcurrencyLimit].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConcurrencyLimitConfig {
+    pub max_concurrency: usize,
+    /// How long a request can wait for a free slot. Requests wait as long as needed when unset.
+    #[serde(default, with = "humantime_serde")]
+    pub max_wait: Option<Duration>,
+}
+
+/// The configuration of a [LoadBalancingPolicy]. Unset fields keep the default of the policy.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoadBalancingConfig {
+    pub strategy: LoadBalancingStrategy,
+    pub ejection_threshold: Option<u32>,
+    #[serde(with = "humantime_serde")]
+    pub ejection_duration: Option<Duration>,
+}
+
+/// The configuration of a [RequestCoalescing].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestCoalescingConfig {
+    /// The headers whose values must be the same for the requests to be coalesced.
+    pub headers: Vec<String>,
+}
+
+/// The configuration of an [IdempotencyKeys]. Unset fields keep the default of the policy.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdempotencyKeysConfig {
+    /// The methods of the requests getting an idempotency key. Default is `POST` and `PATCH`.
+    pub methods: Option<Vec<String>>,
+}
+
+/// The configuration of an in-memory [HttpCache](crate::HttpCache).
+#[cfg_attr(docsrs, doc(cfg(feature = "http-cache")))]
+#[cfg(feature = "http-cache")]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpCacheConfig {
+    /// How long the stale responses that can be revalidated are kept. Default is one day.
+    #[serde(with = "humantime_serde")]
+    pub stale_retention: Option<Duration>,
+}
+
+/// The Auth0 settings of a [BridgeConfig], mirroring [auth0::Config].
+#[cfg_attr(docsrs, doc(cfg(feature = "auth0")))]
+#[cfg(feature = "auth0")]
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Auth0Config {
+    pub token_url: String,
+    pub jwks_url: String,
+    pub caller: String,
+    pub audience: String,
+    #[serde(default = "default_cache_type")]
+    pub cache_type: auth0::CacheType,
+    /// The key used to encrypt the tokens stored in redis. It must be 32 bytes long.
+    #[serde(default)]
+    pub token_encryption_key: String,
+    /// Default is 60 seconds.
+    #[serde(default = "default_check_interval", with = "humantime_serde")]
+    pub check_interval: Duration,
+    /// The range, between 0 and 1, of the lifespan of the token in which it is considered stale.
+    ///
+    /// Default is `[0.6, 0.9]`.
+    pub staleness_check_percentage: Option<(f64, f64)>,
+    pub client_id: String,
+    pub client_secret: String,
+    pub scope: Option<String>,
+}
+
+fn default_environment_proxies() -> bool {
+    true
+}
+
+#[cfg(feature = "auth0")]
+fn default_cache_type() -> auth0::CacheType {
+    auth0::CacheType::Inmemory
+}
+
+#[cfg(feature = "auth0")]
+fn default_check_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+impl BridgeConfig {
+    /// Validates the configuration and creates a [Bridge] from it.
+    ///
+    /// Invalid values are reported as [BridgeBuildError::InvalidConfig], naming the offending field.
+    pub async fn build(self) -> Result<Bridge, BridgeBuildError> {
+        let endpoint = parse_url("endpoint", &self.endpoint)?;
+        let mut builder = Bridge::builder();
+
+        if let Some(user_agent) = self.user_agent {
+            HeaderValue::try_from(user_agent.as_str()).map_err(|error| invalid("user_agent", error))?;
+            builder = builder.with_user_agent(user_agent);
+        }
+        if let Some(redirect_policy) = self.redirect_policy {
+            builder = builder.with_redirect_policy(redirect_policy);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.with_pool_max_idle_per_host(max);
+        }
+        if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+            builder = builder.with_pool_idle_timeout(Some(pool_idle_timeout));
+        }
+        if let Some(default_timeout) = self.default_timeout {
+            builder = builder.with_default_timeout(default_timeout);
+        }
+        if let Some(max_response_bytes) = self.max_response_bytes {
+            builder = builder.with_max_response_bytes(max_response_bytes);
+        }
+        if let Some(request_compression) = self.request_compression {
+            builder = builder.with_request_compression(request_compression.into_compression());
+        }
+
+        if !self.environment_proxies {
+            builder = builder.without_proxies();
+        }
+        if let Some(proxy) = self.proxy {
+            builder = builder.with_proxy(proxy.into_proxy()?);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.with_connect_timeout(connect_timeout);
+        }
+        if let Some(tcp_keepalive) = self.tcp_keepalive {
+            builder = builder.with_tcp_keepalive(Some(tcp_keepalive));
+        }
+        match (self.http1_only, self.http2_prior_knowledge) {
+            (true, true) => return Err(invalid("http2_prior_knowledge", "can't be used with http1_only")),
+            (true, false) => builder = builder.with_http1_only(),
+            (false, true) => builder = builder.with_http2_prior_knowledge(),
+            (false, false) => {}
+        }
+        if let Some(local_address) = self.local_address {
+            builder = builder.with_local_address(local_address);
+        }
+
+        let default_headers = self
+            .default_headers
+            .iter()
+            .map(|(name, value)| {
+                let field = || format!("default_headers.{name}");
+                Ok((
+                    HeaderName::try_from(name.as_str()).map_err(|error| invalid(field(), error))?,
+                    HeaderValue::try_from(value.as_str()).map_err(|error| invalid(field(), error))?,
+                ))
+            })
+            .collect::<Result<Vec<_>, BridgeBuildError>>()?;
+        builder = builder
+            .with_default_headers(default_headers)
+            .with_default_query_pairs(self.default_query_pairs.into_iter().collect());
+
+        if let Some(tls) = self.tls {
+            for (index, path) in tls.root_certificates.iter().enumerate() {
+                let field = format!("tls.root_certificates[{index}]");
+                let pem = read_file(&field, path).await?;
+                tls::parse_certificates(&pem).map_err(|error| invalid(&field, error))?;
+                builder = builder.with_root_certificate(pem);
+            }
+            if let Some(identity) = tls.identity {
+                let identity = match identity {
+                    TlsIdentityConfig::Pem { path } => TlsIdentity::pem(read_file("tls.identity.path", &path).await?),
+                    #[cfg(feature = "native-tls")]
+                    TlsIdentityConfig::Pkcs12 { path, password } => {
+                        TlsIdentity::pkcs12(read_file("tls.identity.path", &path).await?, password)
+                    }
+                };
+                // Parsed upfront, to name the field in the error
+                identity
+                    .clone()
+                    .configure()
+                    .map(drop)
+                    .map_err(|error| invalid("tls.identity", error))?;
+                builder = builder.with_identity(identity);
+            }
+            if let Some(min_version) = tls.min_version {
+                builder = builder.with_tls_min_version(min_version);
+            }
+            builder = builder.danger_accept_invalid_certs(tls.danger_accept_invalid_certs);
+        }
+        if let Some(retry) = self.retry {
+            builder = builder.with_retry_policy(retry.into_policy()?);
+        }
+        if let Some(circuit_breaker) = self.circuit_breaker {
+            builder = builder.with_circuit_breaker(circuit_breaker.into_policy()?);
+        }
+        if let Some(rate_limit) = self.rate_limit {
+            builder = builder.with_rate_limit(rate_limit.into_limit()?);
+        }
+        if let Some(concurrency_limit) = self.concurrency_limit {
+            builder = builder.with_max_concurrency(concurrency_limit.into_limit()?);
+        }
+        if let Some(load_balancing) = self.load_balancing {
+            builder = builder.with_load_balancing(load_balancing.into_policy()?);
+        }
+        if let Some(request_coalescing) = self.request_coalescing {
+            builder = builder.with_request_coalescing(request_coalescing.into_coalescing()?);
+        }
+        if let Some(idempotency_keys) = self.idempotency_keys {
+            builder = builder.with_idempotency_keys(idempotency_keys.into_policy()?);
+        }
+        #[cfg(feature = "http-cache")]
+        if let Some(http_cache) = self.http_cache {
+            builder = builder.with_http_cache(http_cache.into_cache());
+        }
+
+        let mut endpoints = vec![endpoint];
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            endpoints.push(parse_url(&format!("endpoints[{index}]"), endpoint)?);
+        }
+
+        #[cfg(feature = "auth0")]
+        if let Some(auth0) = self.auth0 {
+            let client = reqwest::Client::new();
+            let token = auth0::Auth0::new(&client, auth0.into_config()?)
+                .await?
+                .refreshing_token();
+            builder = builder.with_refreshing_token(token).await;
+        }
+
+        builder.try_build_with_endpoints(endpoints)
+    }
+}
+
+impl RequestCompressionConfig {
+    fn into_compression(self) -> RequestCompression {
+        RequestCompression::new(self.encoding).with_min_size(self.min_size.unwrap_or(compression::DEFAULT_MIN_SIZE))
+    }
+}
+
+impl ProxyConfig {
+    fn into_proxy(self) -> Result<Proxy, BridgeBuildError> {
+        let proxy = Proxy::new(parse_url("proxy.url", &self.url)?, self.scope).with_no_proxy(self.no_proxy);
+        // Checked upfront, to name the field in the error
+        proxy
+            .clone()
+            .into_reqwest()
+            .map_err(|error| invalid("proxy.url", error))?;
+
+        match (self.username, self.password) {
+            (Some(username), password) => Ok(proxy.with_basic_auth(username, password.unwrap_or_default())),
+            (None, Some(_)) => Err(invalid("proxy.password", "requires a username")),
+            (None, None) => Ok(proxy),
+        }
+    }
+}
+
+impl RetryConfig {
+    fn into_policy(self) -> Result<RetryPolicy, BridgeBuildError> {
+        let mut policy = RetryPolicy::default();
+
+        if let Some(max_attempts) = self.max_attempts {
+            if max_attempts == 0 {
+                return Err(invalid("retry.max_attempts", "must be at least 1"));
+            }
+            policy = policy.with_max_attempts(max_attempts);
+        }
+
+        let initial_backoff = self.initial_backoff.unwrap_or(policy.initial_backoff());
+        let max_backoff = self.max_backoff.unwrap_or(policy.max_backoff());
+        if initial_backoff > max_backoff {
+            return Err(invalid("retry.initial_backoff", "must not be greater than max_backoff"));
+        }
+        policy = policy.with_backoff(initial_backoff, max_backoff);
+
+        if let Some(backoff_multiplier) = self.backoff_multiplier {
+            if !backoff_multiplier.is_finite() || backoff_multiplier < 1.0 {
+                return Err(invalid("retry.backoff_multiplier", "must be a number not lower than 1"));
+            }
+            policy = policy.with_backoff_multiplier(backoff_multiplier);
+        }
+        if let Some(jitter) = self.jitter {
+            policy = policy.with_jitter(jitter);
+        }
+        if let Some(status_codes) = self.retryable_status_codes {
+            let status_codes = status_codes
+                .into_iter()
+                .map(|status_code| {
+                    StatusCode::from_u16(status_code).map_err(|error| invalid("retry.retryable_status_codes", error))
+                })
+                .collect::<Result<_, _>>()?;
+            policy = policy.with_retryable_status_codes(status_codes);
+        }
+        if let Some(retryable_errors) = self.retryable_errors {
+            policy = policy.with_retryable_errors(retryable_errors);
+        }
+        if let Some(idempotent_only) = self.idempotent_only {
+            policy = policy.with_idempotent_only(idempotent_only);
+        }
+
+        Ok(policy)
+    }
+}
+
+impl CircuitBreakerConfig {
+    fn into_policy(self) -> Result<CircuitBreakerPolicy, BridgeBuildError> {
+        let mut policy = CircuitBreakerPolicy::default();
+
+        if let Some(threshold) = self.failure_rate_threshold {
+            if !(0.0..=1.0).contains(&threshold) {
+                return Err(invalid(
+                    "circuit_breaker.failure_rate_threshold",
+                    "must be between 0 and 1",
+                ));
+            }
+            policy = policy.with_failure_rate_threshold(threshold);
+        }
+        if let Some(minimum_requests) = self.minimum_requests {
+            if minimum_requests == 0 {
+                return Err(invalid("circuit_breaker.minimum_requests", "must be at least 1"));
+            }
+            policy = policy.with_minimum_requests(minimum_requests);
+        }
+        if let Some(window) = self.window {
+            if window.is_zero() {
+                return Err(invalid("circuit_breaker.window", "must not be zero"));
+            }
+            policy = policy.with_window(window);
+        }
+        if let Some(cool_down) = self.cool_down {
+            policy = policy.with_cool_down(cool_down);
+        }
+        if let Some(half_open_requests) = self.half_open_requests {
+            if half_open_requests == 0 {
+                return Err(invalid("circuit_breaker.half_open_requests", "must be at least 1"));
+            }
+            policy = policy.with_half_open_requests(half_open_requests);
+        }
+
+        Ok(policy)
+    }
+}
+
+impl LoadBalancingConfig {
+    fn into_policy(self) -> Result<LoadBalancingPolicy, BridgeBuildError> {
+        let mut policy = LoadBalancingPolicy::new(self.strategy);
+
+        if let Some(ejection_threshold) = self.ejection_threshold {
+            if ejection_threshold == 0 {
+                return Err(invalid("load_balancing.ejection_threshold", "must be at least 1"));
+            }
+            policy = policy.with_ejection_threshold(ejection_threshold);
+        }
+        if let Some(ejection_duration) = self.ejection_duration {
+            policy = policy.with_ejection_duration(ejection_duration);
+        }
+
+        Ok(policy)
+    }
+}
+
+impl RateLimitConfig {
+    fn into_limit(self) -> Result<RateLimit, BridgeBuildError> {
+        if self.requests == 0 {
+            return Err(invalid("rate_limit.requests", "must be at least 1"));
+        }
+        if self.interval.is_zero() {
+            return Err(invalid("rate_limit.interval", "must not be zero"));
+        }
+
+        let rate_limit = RateLimit::new(self.requests, self.interval);
+        Ok(match self.max_wait {
+            Some(max_wait) => rate_limit.with_max_wait(max_wait),
+            None => rate_limit,
+        })
+    }
+}
+
+impl ConcurrencyLimitConfig {
+    fn into_limit(self) -> Result<ConcurrencyLimit, BridgeBuildError> {
+        if self.max_concurrency == 0 {
+            return Err(invalid("concurrency_limit.max_concurrency", "must be at least 1"));
+        }
+
+        let concurrency_limit = ConcurrencyLimit::new(self.max_concurrency);
+        Ok(match self.max_wait {
+            Some(max_wait) => concurrency_limit.with_max_wait(max_wait),
+            None => concurrency_limit,
+        })
+    }
+}
+
+impl RequestCoalescingConfig {
+    fn into_coalescing(self) -> Result<RequestCoalescing, BridgeBuildError> {
+        self.headers
+            .iter()
+            .enumerate()
+            .try_fold(RequestCoalescing::new(), |coalescing, (index, header)| {
+                let header = HeaderName::try_from(header.as_str())
+                    .map_err(|error| invalid(format!("request_coalescing.headers[{index}]"), error))?;
+                Ok(coalescing.with_header(header))
+            })
+    }
+}
+
+impl IdempotencyKeysConfig {
+    fn into_policy(self) -> Result<IdempotencyKeys, BridgeBuildError> {
+        let Some(methods) = self.methods else {
+            return Ok(IdempotencyKeys::new());
+        };
+        let methods = methods
+            .iter()
+            .enumerate()
+            .map(|(index, method)| {
+                Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                    .map_err(|error| invalid(format!("idempotency_keys.methods[{index}]"), error))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(IdempotencyKeys::new().with_methods(methods))
+    }
+}
+
+#[cfg(feature = "http-cache")]
+impl HttpCacheConfig {
+    fn into_cache(self) -> crate::HttpCache {
+        let cache = crate::HttpCache::in_memory();
+        match self.stale_retention {
+            Some(stale_retention) => cache.with_stale_retention(stale_retention),
+            None => cache,
+        }
+    }
+}
+
+#[cfg(feature = "auth0")]
+impl Auth0Config {
+    fn into_config(self) -> Result<auth0::Config, BridgeBuildError> {
+        let staleness_check_percentage = match self.staleness_check_percentage {
+            Some((min, max)) => {
+                if !(0.0..=1.0).contains(&min) || !(0.0..=1.0).contains(&max) || min > max {
+                    return Err(invalid(
+                        "auth0.staleness_check_percentage",
+                        "must be an ordered pair of numbers between 0 and 1",
+                    ));
+                }
+                auth0::StalenessCheckPercentage::new(min, max)
+            }
+            None => auth0::StalenessCheckPercentage::default(),
+        };
+
+        if matches!(self.cache_type, auth0::CacheType::Redis { .. }) && self.token_encryption_key.len() != 32 {
+            return Err(invalid(
+                "auth0.token_encryption_key",
+                "must be 32 bytes long when caching tokens in redis",
+            ));
+        }
+
+        Ok(auth0::Config {
+            token_url: parse_url("auth0.token_url", &self.token_url)?,
+            jwks_url: parse_url("auth0.jwks_url", &self.jwks_url)?,
+            caller: self.caller,
+            audience: self.audience,
+            cache_type: self.cache_type,
+            token_encryption_key: self.token_encryption_key,
+            check_interval: self.check_interval,
+            staleness_check_percentage,
+            client_id: self.client_id,
+            client_secret: self.client_secret,
+            scope: self.scope,
+        })
+    }
+}
+
+async fn read_file(field: &str, path: &PathBuf) -> Result<Vec<u8>, BridgeBuildError> {
+    tokio::fs::read(path)
+        .await
+        .map_err(|error| invalid(field, format!("{}: {error}", path.display())))
+}
+
+fn parse_url(field: &str, url: &str) -> Result<Url, BridgeBuildError> {
+    Url::parse(url).map_err(|error| invalid(field, error))
+}
+
+fn invalid(field: impl Into<String>, reason: impl ToString) -> BridgeBuildError {
+    BridgeBuildError::InvalidConfig {
+        field: field.into(),
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn config(value: serde_json::Value) -> BridgeConfig {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn deserializes_policies_with_human_readable_durations() {
+        let config = config(json!({
+            "endpoint": "http://prima.it",
+            "redirect_policy": { "limited": 3 },
+            "retry": { "max_attempts": 5, "initial_backoff": "50ms", "retryable_errors": ["connect", "body"] },
+            "rate_limit": { "requests": 10, "interval": "1s", "max_wait": "0s" },
+        }));
+
+        assert_eq!(config.redirect_policy, Some(RedirectPolicy::Limited(3)));
+
+        let retry_policy = config.retry.unwrap().into_policy().unwrap();
+        assert_eq!(retry_policy.max_attempts(), 5);
+        assert_eq!(retry_policy.initial_backoff(), Duration::from_millis(50));
+        assert_eq!(retry_policy.max_backoff(), RetryPolicy::default().max_backoff());
+
+        assert!(config.rate_limit.unwrap().into_limit().is_ok());
+    }
+
+    #[test]
+    fn invalid_values_name_the_field() {
+        let config = config(json!({
+            "endpoint": "http://prima.it",
+            "circuit_breaker": { "failure_rate_threshold": 1.5 },
+        }));
+
+        match config.circuit_breaker.unwrap().into_policy() {
+            Err(BridgeBuildError::InvalidConfig { field, .. }) => {
+                assert_eq!(field, "circuit_breaker.failure_rate_threshold")
+            }
+            Err(other) => panic!("expected InvalidConfig, got: {other:?}"),
+            Ok(_) => panic!("expected InvalidConfig"),
+        }
+    }
+
+    #[test]
+    fn redacts_the_passwords() {
+        let config = config(json!({
+            "endpoint": "http://prima.it",
+            "proxy": { "url": "http://proxy.prima.it", "username": "bridge", "password": "s3cret" },
+        }));
+
+        let debug = format!("{:?}", config.proxy.unwrap());
+        assert!(debug.contains("bridge"));
+        assert!(!debug.contains("s3cret"));
+    }
+
+    #[tokio::test]
+    async fn builds_a_bridge_with_coalescing_idempotency_keys_and_cache() {
+        let mut value = json!({
+            "endpoint": "http://prima.it",
+            "request_coalescing": { "headers": ["accept-language"] },
+            "idempotency_keys": { "methods": ["post", "PUT"] },
+        });
+        if cfg!(feature = "http-cache") {
+            value["http_cache"] = json!({ "stale_retention": "1h" });
+        }
+
+        assert!(config(value).build().await.is_ok());
+    }
+
+    #[test]
+    fn invalid_headers_and_methods_name_the_field() {
+        let config = config(json!({
+            "endpoint": "http://prima.it",
+            "request_coalescing": { "headers": ["accept-language", "not a header"] },
+            "idempotency_keys": { "methods": ["not a method"] },
+        }));
+
+        assert!(matches!(
+            config.request_coalescing.unwrap().into_coalescing(),
+            Err(BridgeBuildError::InvalidConfig { field, .. }) if field == "request_coalescing.headers[1]"
+        ));
+        assert!(matches!(
+            config.idempotency_keys.unwrap().into_policy(),
+            Err(BridgeBuildError::InvalidConfig { field, .. }) if field == "idempotency_keys.methods[0]"
+        ));
+    }
+}
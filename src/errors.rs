@@ -51,10 +51,99 @@ pub enum PrimaBridgeError {
     MalformedVariables,
     #[error("multipart file has invalid mime type: {0}")]
     InvalidMultipartFileMimeType(String),
+    /// The path template of the request can't be expanded with the given parameters.
+    #[error("invalid path template `{template}`: {reason}")]
+    InvalidPathTemplate { template: String, reason: String },
+    /// The response body is larger than the maximum size set on the bridge or on the request, hence it has
+    /// not been read.
+    #[error("the response body of {url} exceeds the limit of {limit} bytes")]
+    ResponseTooLarge { url: Url, limit: u64 },
+    /// A streamed JSON body doesn't have an array at the requested path, or it ended before the array did.
+    #[error("unable to stream the JSON items of {url}: {reason}")]
+    JsonStreamError { url: Url, reason: String },
+    /// The response doesn't have the content type required to read it, eg. `text/event-stream` for the
+    /// [Server-Sent Events](crate::RestRequest::send_sse).
+    #[error("the response of {url} has an unexpected content type: {content_type:?}")]
+    UnexpectedContentType { url: Url, content_type: Option<String> },
+    /// The request has been coalesced with an identical one, which failed. See
+    /// [RequestCoalescing](crate::RequestCoalescing).
+    #[error("the request to {url} has been coalesced with one that failed: {reason}")]
+    CoalescedRequestFailed { url: Url, reason: String },
+    /// The next page of a [paginated](crate::RestRequest::paginate) request can't be requested.
+    #[error("unable to request the page after {url}: {reason}")]
+    PaginationError { url: Url, reason: String },
+    /// The request body could not be encoded with a [Codec](crate::codec::Codec).
+    #[error("failed to encode the {content_type} request body: {source}")]
+    EncodeError {
+        content_type: &'static str,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The response body could not be decoded with a [Codec](crate::codec::Codec) into the expected Rust type.
+    /// For the self-describing formats, it includes the path to the failing field and the structural shape
+    /// of the body at that path, like [DeserializationError](Self::DeserializationError) does.
+    #[error(
+        "failed to decode the {content_type} response body{}: {source}",
+        path.as_ref().map(|path| format!(" at path {path}")).unwrap_or_default()
+    )]
+    DecodeError {
+        content_type: &'static str,
+        path: Option<String>,
+        body_structure: Option<BodyStructure>,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The request body could not be compressed according to the
+    /// [RequestCompression](crate::RequestCompression) of the bridge.
+    #[error("unable to compress the request body: {0}")]
+    RequestBodyCompressionError(#[source] std::io::Error),
+    /// The body of a [StreamingResponse](crate::StreamingResponse) could not be written.
+    #[error("unable to write the response body of {url}: {source}")]
+    ResponseBodyWriteError { url: Url, source: std::io::Error },
     #[error("the response body id not valid utf-8. error: {source}")]
     Utf8Error { source: Utf8Error },
     #[error("some error occurred in a middleware layer")]
     MiddlewareError(reqwest_middleware::Error),
+    /// The circuit breaker of the bridge is open, hence the request has not been sent.
+    #[error("circuit breaker is open, request to {0} has not been sent")]
+    CircuitOpen(Url),
+    /// The rate limit of the bridge has been reached, and the request could not wait for it.
+    #[error("rate limit exceeded, request to {0} has not been sent")]
+    RateLimitExceeded(Url),
+    /// The maximum number of concurrent requests of the bridge has been reached, and the request could not wait
+    /// for a free slot.
+    #[error("concurrency limit exceeded, request to {0} has not been sent")]
+    ConcurrencyLimitExceeded(Url),
+    /// The request has been retried according to its [RetryPolicy](crate::RetryPolicy),
+    /// and the last attempt failed with `source`.
+    #[error("request failed after {attempts} attempts: {source}")]
+    RetriesExhausted {
+        attempts: u32,
+        #[source]
+        source: Box<PrimaBridgeError>,
+    },
+}
+
+/// An error occurred while building a bridge.
+#[derive(Debug, Error)]
+pub enum BridgeBuildError {
+    /// The underlying HTTP client could not be built, eg. because of invalid TLS material or headers.
+    #[error("unable to build the http client: {0}")]
+    HttpClient(#[from] reqwest::Error),
+    /// The TLS material (certificates or keys) given to the builder is invalid.
+    #[error("invalid TLS configuration: {0}")]
+    Tls(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The Auth0 token could not be fetched.
+    #[cfg(feature = "auth0")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "auth0")))]
+    #[error("unable to create the auth0 token: {0}")]
+    Auth0(#[from] crate::auth0::Auth0Error),
+    /// The bridge has been built without any endpoint.
+    #[error("at least one endpoint is required")]
+    NoEndpoints,
+    /// A [BridgeConfig](crate::BridgeConfig) holds an invalid value.
+    #[error("invalid bridge configuration, `{field}`: {reason}")]
+    InvalidConfig { field: String, reason: String },
 }
 
 impl PrimaBridgeError {
@@ -0,0 +1,342 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, AGE, AUTHORIZATION, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH, DATE,
+    ETAG, EXPIRES, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, TRANSFER_ENCODING, VARY,
+};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::Response;
+
+/// A response stored in a [Cache](super::Cache), along with what's needed to tell whether it's still fresh
+/// and to revalidate it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    status: u16,
+    headers: Vec<(String, Vec<u8>)>,
+    body: Vec<u8>,
+    /// When the response has been received, in seconds since the unix epoch.
+    received_at: u64,
+    /// The values of the request headers named by the `Vary` header of the response.
+    vary: Vec<(String, Option<Vec<u8>>)>,
+}
+
+impl CachedResponse {
+    /// Returns the response to be stored, if it can be: a `200 OK` that is either fresh for a while or can be
+    /// revalidated, and whose `Cache-Control` doesn't forbid it.
+    ///
+    /// Since the cache is shared by all the callers, the `private` responses aren't stored, and neither are the
+    /// ones to requests with an `Authorization` header, unless they're `public` or have an `s-maxage`.
+    pub(crate) fn storable(response: &Response, request_headers: &HeaderMap, received_at: SystemTime) -> Option<Self> {
+        let headers = response.headers();
+        if *response.status_code() != StatusCode::OK
+            || has_directive(headers, "no-store")
+            || has_directive(headers, "private")
+        {
+            return None;
+        }
+        let shareable = has_directive(headers, "public") || has_directive(headers, "s-maxage");
+        if request_headers.contains_key(AUTHORIZATION) && !shareable {
+            return None;
+        }
+
+        let mut vary = vec![];
+        for name in header_list(headers, &VARY) {
+            if name == "*" {
+                return None;
+            }
+            let value = request_headers.get(&name).map(|value| value.as_bytes().to_vec());
+            vary.push((name, value));
+        }
+
+        let cached = Self {
+            status: response.status_code().as_u16(),
+            headers: to_pairs(headers),
+            body: response.raw_body().clone(),
+            received_at: unix_seconds(received_at),
+            vary,
+        };
+        (cached.freshness_lifetime() > Duration::ZERO || !cached.validators().is_empty()).then_some(cached)
+    }
+
+    pub(crate) fn headers(&self) -> HeaderMap {
+        self.headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_bytes(value).ok()?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns whether the response has been stored for a request with the same values of the headers named by
+    /// its `Vary` header.
+    pub(crate) fn varies_like(&self, request_headers: &HeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| request_headers.get(name).map(|value| value.as_bytes()) == value.as_deref())
+    }
+
+    /// Returns how long the response is fresh after being generated, from its `Cache-Control` or `Expires`
+    /// header. Without either, it has to be revalidated every time.
+    fn freshness_lifetime(&self) -> Duration {
+        let headers = self.headers();
+        if has_directive(&headers, "no-cache") {
+            return Duration::ZERO;
+        }
+        if let Some(max_age) = directive(&headers, "max-age") {
+            return Duration::from_secs(max_age.and_then(|max_age| max_age.parse().ok()).unwrap_or_default());
+        }
+
+        let date = http_date(&headers, &DATE).unwrap_or(UNIX_EPOCH + Duration::from_secs(self.received_at));
+        http_date(&headers, &EXPIRES)
+            .and_then(|expires| expires.duration_since(date).ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns the age of the response, as stated by the server when it's been received plus the time passed
+    /// since then.
+    fn age(&self, now: SystemTime) -> Duration {
+        let age = self
+            .headers()
+            .get(AGE)
+            .and_then(|age| age.to_str().ok()?.trim().parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or_default();
+        let resident_time = now
+            .duration_since(UNIX_EPOCH + Duration::from_secs(self.received_at))
+            .unwrap_or_default();
+        age + resident_time
+    }
+
+    pub(crate) fn is_fresh(&self, now: SystemTime) -> bool {
+        self.freshness_lifetime() > self.age(now)
+    }
+
+    /// Returns how long the response has to be kept: until it becomes stale, and for the given retention
+    /// afterwards if it can be revalidated.
+    pub(crate) fn time_to_live(&self, now: SystemTime, stale_retention: Duration) -> Duration {
+        let fresh_for = self.freshness_lifetime().saturating_sub(self.age(now));
+        match self.validators().is_empty() {
+            true => fresh_for,
+            false => fresh_for + stale_retention,
+        }
+    }
+
+    /// Returns the conditional request headers revalidating the response, from its `ETag` and `Last-Modified`
+    /// headers.
+    pub(crate) fn validators(&self) -> Vec<(HeaderName, HeaderValue)> {
+        let headers = self.headers();
+        [(ETAG, IF_NONE_MATCH), (LAST_MODIFIED, IF_MODIFIED_SINCE)]
+            .into_iter()
+            .filter_map(|(validator, condition)| Some((condition, headers.get(validator)?.clone())))
+            .collect()
+    }
+
+    /// Updates the response with the headers of the `304 Not Modified` revalidating it.
+    pub(crate) fn revalidated(self, not_modified_headers: &HeaderMap, received_at: SystemTime) -> Self {
+        let mut headers = self.headers();
+        // The age is relative to the new response
+        headers.remove(AGE);
+        // The headers describing the body of the 304 don't apply to the stored one
+        let updated: Vec<_> = not_modified_headers
+            .iter()
+            .filter(|(name, _)| ![CONTENT_LENGTH, CONTENT_ENCODING, TRANSFER_ENCODING].contains(name))
+            .collect();
+        for (name, _) in &updated {
+            headers.remove(*name);
+        }
+        for (name, value) in updated {
+            headers.append(name, value.clone());
+        }
+
+        Self {
+            headers: to_pairs(&headers),
+            received_at: unix_seconds(received_at),
+            ..self
+        }
+    }
+
+    pub(crate) fn into_response(self, url: Url, request_id: Uuid) -> Response {
+        let headers = self.headers();
+        Response::rest(
+            url,
+            self.body,
+            StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK),
+            headers,
+            request_id,
+        )
+    }
+}
+
+/// Returns the directives of the `Cache-Control` headers, with their values if they have one.
+fn directives(headers: &HeaderMap) -> impl Iterator<Item = (String, Option<String>)> + '_ {
+    header_list(headers, &CACHE_CONTROL).map(|directive| match directive.split_once('=') {
+        Some((name, value)) => (
+            name.trim().to_string(),
+            Some(value.trim().trim_matches('"').to_string()),
+        ),
+        None => (directive, None),
+    })
+}
+
+pub(crate) fn directive(headers: &HeaderMap, name: &str) -> Option<Option<String>> {
+    directives(headers)
+        .find(|(directive, _)| directive == name)
+        .map(|(_, value)| value)
+}
+
+pub(crate) fn has_directive(headers: &HeaderMap, name: &str) -> bool {
+    directive(headers, name).is_some()
+}
+
+/// Returns the lowercase elements of a comma separated header, like `Cache-Control` or `Vary`.
+fn header_list<'h>(headers: &'h HeaderMap, name: &HeaderName) -> impl Iterator<Item = String> + 'h {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|element| element.trim().to_ascii_lowercase())
+        .filter(|element| !element.is_empty())
+}
+
+fn http_date(headers: &HeaderMap, name: &HeaderName) -> Option<SystemTime> {
+    httpdate::parse_http_date(headers.get(name)?.to_str().ok()?).ok()
+}
+
+fn to_pairs(headers: &HeaderMap) -> Vec<(String, Vec<u8>)> {
+    headers
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+        .collect()
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(headers: &[(&str, &str)], received_at: SystemTime) -> Option<CachedResponse> {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                (
+                    HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                    HeaderValue::from_str(value).unwrap(),
+                )
+            })
+            .collect();
+        let response = Response::rest(
+            "http://localhost/items".parse().unwrap(),
+            b"[]".to_vec(),
+            StatusCode::OK,
+            headers,
+            Uuid::new_v4(),
+        );
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert("accept-language", HeaderValue::from_static("it"));
+        CachedResponse::storable(&response, &request_headers, received_at)
+    }
+
+    #[test]
+    fn is_fresh_for_its_max_age() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let cached = cached(&[("cache-control", "public, max-age=60"), ("age", "20")], now).unwrap();
+
+        assert!(cached.is_fresh(now + Duration::from_secs(39)));
+        assert!(!cached.is_fresh(now + Duration::from_secs(40)));
+        assert_eq!(
+            cached.time_to_live(now, Duration::from_secs(3600)),
+            Duration::from_secs(40)
+        );
+    }
+
+    #[test]
+    fn is_fresh_until_it_expires() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let date = httpdate::fmt_http_date(now);
+        let expires = httpdate::fmt_http_date(now + Duration::from_secs(30));
+        let cached = cached(&[("date", &date), ("expires", &expires)], now).unwrap();
+
+        assert!(cached.is_fresh(now + Duration::from_secs(29)));
+        assert!(!cached.is_fresh(now + Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn keeps_the_stale_responses_that_can_be_revalidated() {
+        let now = SystemTime::now();
+        let cached = cached(&[("cache-control", "no-cache, max-age=60"), ("etag", "\"v1\"")], now).unwrap();
+
+        assert!(!cached.is_fresh(now));
+        assert_eq!(
+            cached.time_to_live(now, Duration::from_secs(3600)),
+            Duration::from_secs(3600)
+        );
+        assert_eq!(
+            cached.validators(),
+            vec![(IF_NONE_MATCH, HeaderValue::from_static("\"v1\""))]
+        );
+    }
+
+    #[test]
+    fn stores_only_the_responses_that_can_be_reused() {
+        let now = SystemTime::now();
+
+        assert!(cached(&[], now).is_none());
+        assert!(cached(&[("cache-control", "no-store, max-age=60")], now).is_none());
+        assert!(cached(&[("cache-control", "max-age=60"), ("vary", "*")], now).is_none());
+        assert!(cached(&[("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT")], now).is_some());
+        assert!(cached(&[("cache-control", "private, max-age=60")], now).is_none());
+    }
+
+    #[test]
+    fn matches_the_requests_with_the_same_varying_headers() {
+        let cached = cached(
+            &[("cache-control", "max-age=60"), ("vary", "Accept-Language, Accept")],
+            SystemTime::now(),
+        )
+        .unwrap();
+        let mut request_headers = HeaderMap::new();
+
+        request_headers.insert("accept-language", HeaderValue::from_static("it"));
+        assert!(cached.varies_like(&request_headers));
+
+        request_headers.insert("accept", HeaderValue::from_static("application/json"));
+        assert!(!cached.varies_like(&request_headers));
+    }
+
+    #[test]
+    fn updates_the_headers_when_revalidated() {
+        let now = SystemTime::now();
+        let cached = cached(
+            &[
+                ("cache-control", "no-cache"),
+                ("etag", "\"v1\""),
+                ("x-version", "1"),
+                ("age", "10"),
+            ],
+            now,
+        )
+        .unwrap();
+        let mut not_modified_headers = HeaderMap::new();
+        not_modified_headers.insert(CACHE_CONTROL, HeaderValue::from_static("max-age=60"));
+        not_modified_headers.insert(CONTENT_LENGTH, HeaderValue::from_static("0"));
+
+        let revalidated = cached.revalidated(&not_modified_headers, now);
+
+        let headers = revalidated.headers();
+        assert_eq!(headers.get(CACHE_CONTROL).unwrap(), "max-age=60");
+        assert_eq!(headers.get("x-version").unwrap(), "1");
+        assert!(headers.get(AGE).is_none());
+        assert!(headers.get(CONTENT_LENGTH).is_none());
+        assert!(revalidated.is_fresh(now));
+    }
+}
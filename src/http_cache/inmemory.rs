@@ -0,0 +1,156 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use super::{Cache, CacheError, CachedResponse};
+
+/// How many responses an [InMemoryCache] keeps by default.
+const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
+/// A [Cache] keeping the responses in memory, up to a maximum number of them. Its clones share the responses.
+///
+/// The responses past their time to live are dropped when they are read, or when a new response is stored.
+/// When the cache is full, the responses expiring first make room for the new ones.
+#[derive(Clone, Debug)]
+pub struct InMemoryCache {
+    max_entries: usize,
+    entries: Arc<Mutex<Entries>>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    responses: HashMap<String, (CachedResponse, Expiration)>,
+    /// The keys of the responses, by when they expire.
+    expirations: BTreeMap<Expiration, String>,
+    next_sequence: u64,
+}
+
+/// When a response expires, along with a sequence number telling apart the ones expiring at the same instant.
+type Expiration = (Instant, u64);
+
+impl Default for InMemoryCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ENTRIES)
+    }
+}
+
+impl InMemoryCache {
+    /// Creates a cache keeping at most the given number of responses. Default is 10000.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            entries: Default::default(),
+        }
+    }
+}
+
+impl Entries {
+    fn remove(&mut self, key: &str) {
+        if let Some((_, expiration)) = self.responses.remove(key) {
+            self.expirations.remove(&expiration);
+        }
+    }
+
+    /// Drops the first response to expire, if it expired before the given instant.
+    fn pop_expiring_before(&mut self, instant: Option<Instant>) -> bool {
+        let Some(entry) = self.expirations.first_entry() else {
+            return false;
+        };
+        if instant.is_some_and(|instant| entry.key().0 > instant) {
+            return false;
+        }
+        let key = entry.remove();
+        self.responses.remove(&key);
+        true
+    }
+}
+
+#[async_trait::async_trait]
+impl Cache for InMemoryCache {
+    async fn get_response(&self, key: &str) -> Result<Option<CachedResponse>, CacheError> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let (response, (expires_at, _)) = match entries.responses.get(key) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        if *expires_at > Instant::now() {
+            return Ok(Some(response.clone()));
+        }
+        entries.remove(key);
+        Ok(None)
+    }
+
+    async fn put_response(&self, key: &str, response: &CachedResponse, ttl: Duration) -> Result<(), CacheError> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.remove(key);
+        while entries.pop_expiring_before(Some(now)) {}
+        while entries.responses.len() >= self.max_entries && entries.pop_expiring_before(None) {}
+
+        let expiration = (now + ttl, entries.next_sequence);
+        entries.next_sequence += 1;
+        entries.expirations.insert(expiration, key.to_string());
+        entries
+            .responses
+            .insert(key.to_string(), (response.clone(), expiration));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use reqwest::header::{HeaderMap, HeaderValue, CACHE_CONTROL};
+    use reqwest::StatusCode;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::Response;
+
+    fn cached() -> CachedResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("max-age=60"));
+        let response = Response::rest(
+            "http://localhost/items".parse().unwrap(),
+            b"[]".to_vec(),
+            StatusCode::OK,
+            headers,
+            Uuid::new_v4(),
+        );
+        CachedResponse::storable(&response, &HeaderMap::new(), SystemTime::now()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn evicts_the_responses_expiring_first_when_full() {
+        let cache = InMemoryCache::new(2);
+        let minute = Duration::from_secs(60);
+
+        cache.put_response("a", &cached(), minute * 3).await.unwrap();
+        cache.put_response("b", &cached(), minute).await.unwrap();
+        cache.put_response("c", &cached(), minute * 2).await.unwrap();
+
+        assert!(cache.get_response("a").await.unwrap().is_some());
+        assert!(cache.get_response("b").await.unwrap().is_none());
+        assert!(cache.get_response("c").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn drops_the_expired_responses() {
+        let cache = InMemoryCache::new(10);
+
+        cache.put_response("a", &cached(), Duration::ZERO).await.unwrap();
+        cache
+            .put_response("b", &cached(), Duration::from_secs(60))
+            .await
+            .unwrap();
+        cache
+            .put_response("b", &cached(), Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        let entries = cache.entries.lock().unwrap();
+        assert_eq!(entries.responses.len(), 1);
+        assert_eq!(entries.expirations.len(), 1);
+    }
+}
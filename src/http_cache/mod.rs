@@ -0,0 +1,155 @@
+//! A cache of the responses to the `GET` requests made by a [Bridge](crate::Bridge), following the HTTP
+//! caching rules: see [HttpCache].
+
+use std::error::Error;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use reqwest::header::{IF_MODIFIED_SINCE, IF_NONE_MATCH};
+use reqwest::StatusCode;
+
+pub use cached_response::CachedResponse;
+pub use inmemory::InMemoryCache;
+#[cfg(feature = "http-cache-redis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http-cache-redis")))]
+pub use redis_impl::RedisCache;
+
+use crate::errors::{PrimaBridgeError, PrimaBridgeResult};
+use crate::{DeliverableRequest, Response};
+
+mod cached_response;
+pub mod inmemory;
+#[cfg(feature = "http-cache-redis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http-cache-redis")))]
+pub mod redis_impl;
+
+/// How long the stale responses that can be revalidated are kept by default.
+const DEFAULT_STALE_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(thiserror::Error, Debug)]
+#[error(transparent)]
+pub struct CacheError(pub Box<dyn Error + Send + Sync>);
+
+/// The storage of an [HttpCache].
+#[async_trait::async_trait]
+pub trait Cache: Send + Sync + std::fmt::Debug {
+    async fn get_response(&self, key: &str) -> Result<Option<CachedResponse>, CacheError>;
+
+    /// Stores the response, which can be dropped after the given time to live.
+    async fn put_response(&self, key: &str, response: &CachedResponse, ttl: Duration) -> Result<(), CacheError>;
+}
+
+/// A cache of the responses to the `GET` requests made by a [Bridge](crate::Bridge), set with
+/// [with_http_cache](crate::builder::BridgeBuilderInner::with_http_cache).
+///
+/// The `200 OK` responses are reused while they are fresh according to their `Cache-Control: max-age` or
+/// `Expires` header, unless they set `no-store`. Once stale, the ones with an `ETag` or a `Last-Modified`
+/// header are revalidated with a conditional request, and a `304 Not Modified` gets the cached [Response]
+/// back, with the updated headers.
+///
+/// The cache is shared by all the callers of the bridge, and possibly by its replicas: the `private` responses
+/// aren't stored, and neither are the ones to requests with an `Authorization` header, unless they are
+/// `public` or have an `s-maxage`.
+///
+/// A request can skip the cache with its own `Cache-Control: no-store` header, or force the revalidation
+/// with `Cache-Control: no-cache`.
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    cache: Arc<dyn Cache>,
+    stale_retention: Duration,
+}
+
+impl HttpCache {
+    /// Creates a cache storing the responses in the given [Cache].
+    pub fn new(cache: impl Cache + 'static) -> Self {
+        Self {
+            cache: Arc::new(cache),
+            stale_retention: DEFAULT_STALE_RETENTION,
+        }
+    }
+
+    /// Creates a cache storing the responses in memory, shared by the clones of the bridge.
+    pub fn in_memory() -> Self {
+        Self::new(InMemoryCache::default())
+    }
+
+    /// Sets how long the stale responses that can be revalidated are kept. Default is one day.
+    pub fn with_stale_retention(self, stale_retention: Duration) -> Self {
+        Self {
+            stale_retention,
+            ..self
+        }
+    }
+
+    /// Sends a `GET` request, unless a fresh response is cached.
+    pub(crate) async fn send<'a, R: DeliverableRequest<'a> + Send>(
+        &self,
+        mut request: R,
+    ) -> PrimaBridgeResult<Response> {
+        let mut request_headers = request.get_bridge().default_headers.clone();
+        request_headers.extend(request.get_custom_headers().clone());
+        #[cfg(feature = "auth0")]
+        request_headers.extend(request.get_auth0_headers());
+        // Conditional requests are the caller's own business
+        let is_conditional =
+            request_headers.contains_key(IF_NONE_MATCH) || request_headers.contains_key(IF_MODIFIED_SINCE);
+        if is_conditional || cached_response::has_directive(&request_headers, "no-store") {
+            return request.send_response().await;
+        }
+        let must_revalidate = cached_response::has_directive(&request_headers, "no-cache")
+            || cached_response::directive(&request_headers, "max-age") == Some(Some("0".to_string()));
+
+        let url = request.get_url();
+        let key = format!("GET {url}");
+        let cached = match self.cache.get_response(&key).await {
+            Ok(cached) => cached.filter(|cached| cached.varies_like(&request_headers)),
+            Err(error) => {
+                tracing::warn!(%url, %error, "unable to read the cached response");
+                None
+            }
+        };
+
+        let validators = match cached {
+            Some(cached) if !must_revalidate && cached.is_fresh(SystemTime::now()) => {
+                return Ok(cached.into_response(url, request.get_id()));
+            }
+            Some(ref cached) => cached.validators(),
+            None => vec![],
+        };
+        let ignore_status_code = request.get_ignore_status_code();
+        let revalidating = !validators.is_empty();
+        if revalidating {
+            request.get_custom_headers_mut().extend(validators);
+            // The 304 isn't an error
+            request = request.ignore_status_code();
+        }
+
+        let request_id = request.get_id();
+        let response: Response = request.send_response().await?;
+        let received_at = SystemTime::now();
+
+        if let (true, Some(cached), StatusCode::NOT_MODIFIED) = (revalidating, cached, *response.status_code()) {
+            let cached = cached.revalidated(response.headers(), received_at);
+            self.put(&key, &cached, received_at).await;
+            return Ok(cached.into_response(url, request_id));
+        }
+        if !ignore_status_code && !response.is_ok() {
+            return Err(PrimaBridgeError::WrongStatusCode(url, *response.status_code()));
+        }
+        if let Some(cached) = CachedResponse::storable(&response, &request_headers, received_at) {
+            self.put(&key, &cached, received_at).await;
+        }
+
+        Ok(response)
+    }
+
+    async fn put(&self, key: &str, cached: &CachedResponse, now: SystemTime) {
+        let ttl = cached.time_to_live(now, self.stale_retention);
+        if ttl.is_zero() {
+            return;
+        }
+        if let Err(error) = self.cache.put_response(key, cached, ttl).await {
+            tracing::warn!(key, %error, "unable to cache the response");
+        }
+    }
+}
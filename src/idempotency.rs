@@ -0,0 +1,77 @@
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Method;
+use uuid::Uuid;
+
+use crate::DeliverableRequest;
+
+/// The header carrying the idempotency key of a request.
+pub(crate) const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Adds an `Idempotency-Key` header to the requests made by a [Bridge](crate::Bridge) with the given methods,
+/// `POST` and `PATCH` by default, so that the server can tell the retries of a request apart from new ones.
+///
+/// The key is generated from the id of the request, and stays the same across all its attempts. Requests
+/// setting their own key, with [with_custom_idempotency_key](crate::RestRequest::with_custom_idempotency_key)
+/// or the `Idempotency-Key` header, keep it.
+///
+/// The requests with an idempotency key are retried by a [RetryPolicy](crate::RetryPolicy) as if their
+/// method were idempotent.
+#[derive(Debug, Clone)]
+pub struct IdempotencyKeys {
+    methods: Vec<Method>,
+}
+
+impl Default for IdempotencyKeys {
+    fn default() -> Self {
+        Self {
+            methods: vec![Method::POST, Method::PATCH],
+        }
+    }
+}
+
+impl IdempotencyKeys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the methods of the requests getting an idempotency key.
+    pub fn with_methods(self, methods: Vec<Method>) -> Self {
+        Self { methods }
+    }
+}
+
+/// The idempotency key set on a single request.
+#[derive(Debug, Clone)]
+pub(crate) enum IdempotencyKey {
+    /// Generated from the id of the request, hence different for every clone of it.
+    RequestId,
+    Custom(HeaderValue),
+}
+
+impl IdempotencyKey {
+    pub(crate) fn value(&self, request_id: Uuid) -> HeaderValue {
+        match self {
+            Self::RequestId => request_id_key(request_id),
+            Self::Custom(key) => key.clone(),
+        }
+    }
+}
+
+/// Returns the idempotency key of a request which doesn't set one explicitly: the value of its
+/// `Idempotency-Key` header, or the one required by the [IdempotencyKeys] of the bridge.
+pub(crate) fn default_key<'a, R: DeliverableRequest<'a>>(request: &R) -> Option<HeaderValue> {
+    if let Some(key) = request.get_custom_headers().get(IDEMPOTENCY_KEY) {
+        return Some(key.clone());
+    }
+    let method = request.get_method();
+    request
+        .get_bridge()
+        .idempotency_keys
+        .as_ref()
+        .filter(|idempotency_keys| idempotency_keys.methods.contains(&method))
+        .map(|_| request_id_key(request.get_id()))
+}
+
+fn request_id_key(request_id: Uuid) -> HeaderValue {
+    HeaderValue::from_str(&request_id.to_string()).expect("a uuid is a valid header value")
+}
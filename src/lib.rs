@@ -19,7 +19,14 @@
 //!
 //! ### Features
 //! * `auth0` - enable auth0 integration, allowing bridge.rs to retrieve tokens from auth0  for authentication
-//! * `gzip` - provides response body gzip decompression.
+//! * `gzip` - provides response body gzip decompression, and request body gzip compression with [RequestCompression].
+//! * `brotli` - provides response body brotli decompression.
+//! * `deflate` - provides response body deflate decompression.
+//! * `zstd` - provides response body zstd decompression, and request body zstd compression with [RequestCompression].
+//! * `msgpack`, `cbor`, `xml`, `protobuf` - provide the [codec]s for MessagePack, CBOR, XML and Protocol Buffers
+//!   bodies.
+//! * `http-cache` - provides the [HttpCache] of the responses to the `GET` requests, stored in memory.
+//! * `http-cache-redis` - provides the [http_cache::RedisCache], to store the cached responses in Redis.
 //! * `redis-tls` - add support for connecting to redis with tls
 //! * `grpc` - provides the [GrpcOtelInterceptor] for adding the opentelemetry context to the gRPC requests
 //! * `tracing_opentelemetry` - adds support for integration with opentelemetry.
@@ -30,31 +37,67 @@
 #[cfg(feature = "auth0")]
 #[cfg_attr(docsrs, doc(cfg(feature = "auth0")))]
 use auth0::RefreshingToken;
-use errors::PrimaBridgeError;
+use circuit_breaker::CircuitBreaker;
+use coalescing::Coalescer;
+use errors::{PrimaBridgeError, PrimaBridgeResult};
+use futures::StreamExt;
 use http::{header::HeaderName, HeaderValue, Method};
+use limit::{ConcurrencyLimiter, RateLimiter};
+use load_balancer::LoadBalancer;
 use reqwest::{multipart::Form, Url};
 use sealed::Sealed;
 
 pub use self::{
-    builder::BridgeBuilder,
+    batch::{BatchOrder, BatchStream},
+    builder::{BridgeBuilder, MiddlewareBridgeBuilder},
+    circuit_breaker::{CircuitBreakerPolicy, CircuitState},
+    coalescing::RequestCoalescing,
+    compression::{ContentEncoding, RequestCompression},
+    config::BridgeConfig,
+    idempotency::IdempotencyKeys,
+    limit::{ConcurrencyLimit, RateLimit},
+    load_balancer::{LoadBalancingPolicy, LoadBalancingStrategy},
+    proxy::{Proxy, ProxyScope},
     redirect::RedirectPolicy,
     request::{
-        Body, DeliverableRequest, GraphQLMultipart, GraphQLRequest, MultipartFile, MultipartFormFileField, Request,
-        RestMultipart, RestRequest,
+        ArrayEncoding, Body, DeliverableRequest, GraphQLMultipart, GraphQLRequest, MultipartFile,
+        MultipartFormFileField, MultipartPart, PaginatedStream, Pagination, Request, RestMultipart, RestRequest,
     },
     response::graphql::{Error, ParsedGraphqlResponse, ParsedGraphqlResponseExt, PossiblyParsedData},
-    response::Response,
+    response::{BodyStream, EventStream, JsonStream, Response, ServerSentEvent, StreamingResponse},
+    retry::{RetryPolicy, RetryableError},
+    tls::{TlsIdentity, TlsVersion},
 };
+#[cfg(feature = "http-cache")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http-cache")))]
+pub use http_cache::HttpCache;
 #[cfg(all(feature = "grpc", feature = "_any_otel_version"))]
 #[cfg_attr(docsrs, doc(cfg(feature = "grpc")))]
 pub use request::grpc::{GrpcOtelInterceptedService, GrpcOtelInterceptor};
 
+mod batch;
 pub mod builder;
+mod circuit_breaker;
+mod coalescing;
+pub mod codec;
+mod compression;
+pub mod config;
+#[cfg(any(feature = "auth0", feature = "http-cache-redis"))]
+mod crypto;
 mod errors;
+#[cfg(feature = "http-cache")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http-cache")))]
+pub mod http_cache;
+mod idempotency;
+mod limit;
+mod load_balancer;
 pub mod prelude;
+mod proxy;
 mod redirect;
 mod request;
 mod response;
+mod retry;
+mod tls;
 
 #[cfg(feature = "auth0")]
 #[cfg_attr(docsrs, doc(cfg(feature = "auth0")))]
@@ -69,8 +112,22 @@ pub type Bridge = BridgeImpl<reqwest::Client>;
 pub struct BridgeImpl<T: BridgeClient> {
     inner_client: T,
     endpoint: Url,
+    load_balancer: Option<LoadBalancer>,
     #[cfg(feature = "auth0")]
     auth0_opt: Option<RefreshingToken>,
+    retry_policy: Option<RetryPolicy>,
+    circuit_breaker: Option<CircuitBreaker>,
+    rate_limiter: Option<RateLimiter>,
+    concurrency_limiter: Option<ConcurrencyLimiter>,
+    default_headers: http::HeaderMap,
+    default_query_pairs: Vec<(String, String)>,
+    default_timeout: std::time::Duration,
+    max_response_bytes: Option<u64>,
+    request_compression: Option<RequestCompression>,
+    coalescer: Option<Coalescer>,
+    idempotency_keys: Option<IdempotencyKeys>,
+    #[cfg(feature = "http-cache")]
+    http_cache: Option<http_cache::HttpCache>,
 }
 
 /// A trait that abstracts the client used by the [BridgeImpl], such that both reqwest clients and reqwest
@@ -97,6 +154,12 @@ pub trait PrimaRequestBuilderInner: Send + Sealed {
 
     fn body(self, body: impl Into<reqwest::Body>) -> Self;
     fn multipart(self, multipart: Form) -> Self;
+    fn try_clone(&self) -> Option<Self>
+    where
+        Self: Sized;
+    fn with_url(self, url: Url) -> reqwest::Result<Self>
+    where
+        Self: Sized;
     async fn send(self, url: Url) -> Result<reqwest::Response, PrimaBridgeError>;
 }
 
@@ -167,6 +230,21 @@ impl<T: PrimaRequestBuilderInner> PrimaRequestBuilder<T> {
         }
     }
 
+    fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            url: self.url.clone(),
+            inner: self.inner.try_clone()?,
+        })
+    }
+
+    /// Sends the request to another URL.
+    fn with_url(self, url: Url) -> Result<Self, PrimaBridgeError> {
+        match self.inner.with_url(url.clone()) {
+            Ok(inner) => Ok(Self { url, inner }),
+            Err(source) => Err(PrimaBridgeError::HttpError { url, source }),
+        }
+    }
+
     async fn send(self) -> Result<reqwest::Response, PrimaBridgeError> {
         self.inner.send(self.url).await
     }
@@ -197,6 +275,15 @@ impl PrimaRequestBuilderInner for reqwest::RequestBuilder {
     fn multipart(self, multipart: Form) -> Self {
         self.multipart(multipart)
     }
+    fn try_clone(&self) -> Option<Self> {
+        self.try_clone()
+    }
+    fn with_url(self, url: Url) -> reqwest::Result<Self> {
+        let (client, request) = self.build_split();
+        let mut request = request?;
+        *request.url_mut() = url;
+        Ok(Self::from_parts(client, request))
+    }
     async fn send(self, url: Url) -> Result<reqwest::Response, PrimaBridgeError> {
         self.send().await.map_err(|e| PrimaBridgeError::HttpError {
             source: e,
@@ -232,6 +319,17 @@ impl PrimaRequestBuilderInner for reqwest_middleware::RequestBuilder {
         self.multipart(multipart)
     }
 
+    fn try_clone(&self) -> Option<Self> {
+        self.try_clone()
+    }
+
+    fn with_url(self, url: Url) -> reqwest::Result<Self> {
+        let (client, request) = self.build_split();
+        let mut request = request?;
+        *request.url_mut() = url;
+        Ok(Self::from_parts(client, request))
+    }
+
     async fn send(self, url: Url) -> Result<reqwest::Response, PrimaBridgeError> {
         self.send().await.map_err(|e| match e {
             reqwest_middleware::Error::Reqwest(e) => PrimaBridgeError::HttpError {
@@ -259,15 +357,82 @@ impl Bridge {
     }
 }
 
+impl<T: BridgeClient> BridgeImpl<T> {
+    /// Returns the state of the circuit breaker, if it has been configured via
+    /// [BridgeBuilder.with_circuit_breaker](BridgeBuilder#method.with_circuit_breaker).
+    ///
+    /// This is meant to be exposed by health checks.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(CircuitBreaker::state)
+    }
+
+    /// Creates a bridge sending its requests to another endpoint, sharing the client, and so the connection
+    /// pool, the Auth0 token and the defaults of this one.
+    ///
+    /// The circuit breaker and the limits of the new bridge have the same policies as the ones of this bridge,
+    /// but keep their own state, since they protect another service.
+    pub fn with_endpoint(&self, endpoint: Url) -> Self {
+        Self {
+            endpoint,
+            load_balancer: None,
+            circuit_breaker: self.circuit_breaker.as_ref().map(CircuitBreaker::detached),
+            rate_limiter: self.rate_limiter.as_ref().map(RateLimiter::detached),
+            concurrency_limiter: self.concurrency_limiter.as_ref().map(ConcurrencyLimiter::detached),
+            ..self.clone()
+        }
+    }
+
+    /// Sends the requests, at most `concurrency` at a time, and returns their results in the same order.
+    ///
+    /// The spans of the requests are children of a `prima_bridge.batch` span, which records how many of them
+    /// succeeded and failed.
+    pub async fn send_all<'a, R>(
+        requests: impl IntoIterator<Item = R>,
+        concurrency: usize,
+    ) -> Vec<PrimaBridgeResult<Response>>
+    where
+        R: DeliverableRequest<'a, Client = T> + Send,
+    {
+        batch::send_all(requests, concurrency, BatchOrder::Input)
+            .collect()
+            .await
+    }
+
+    /// Sends the requests, at most `concurrency` at a time, streaming their results in the given [BatchOrder].
+    ///
+    /// The requests are sent as the stream is polled, and the ones not sent yet are dropped along with it.
+    /// They are traced like the ones of [send_all](BridgeImpl::send_all).
+    pub fn send_all_streaming<'a, R>(
+        requests: impl IntoIterator<Item = R>,
+        concurrency: usize,
+        order: BatchOrder,
+    ) -> BatchStream<'a>
+    where
+        R: DeliverableRequest<'a, Client = T> + Send,
+    {
+        batch::send_all(requests, concurrency, order)
+    }
+}
+
 mod sealed {
     use crate::BridgeClient;
 
     pub trait Sealed {}
 
+    /// The parts of a [DeliverableRequest](crate::DeliverableRequest) which aren't public.
+    pub trait SealedRequest: Sealed {
+        /// Returns the timeout the request is sent with, if it has any.
+        fn request_timeout(&self) -> Option<std::time::Duration>;
+    }
+
     impl Sealed for reqwest::Client {}
+    impl Sealed for reqwest::ClientBuilder {}
+    impl Sealed for crate::builder::MiddlewareClientBuilder {}
     impl Sealed for reqwest_middleware::ClientWithMiddleware {}
     impl Sealed for reqwest_middleware::RequestBuilder {}
     impl Sealed for reqwest::RequestBuilder {}
+    impl Sealed for crate::Response {}
+    impl Sealed for crate::StreamingResponse {}
     impl<Client: BridgeClient> Sealed for crate::request::RestRequest<'_, Client> {}
     impl<Client: BridgeClient> Sealed for crate::request::GraphQLRequest<'_, Client> {}
 }
@@ -0,0 +1,215 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::Url;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::errors::{PrimaBridgeError, PrimaBridgeResult};
+
+/// A client-side rate limit, implemented as a token bucket.
+///
+/// The bucket holds up to `requests` tokens and gets refilled at a steady pace of `requests` tokens every
+/// `interval`. Every request sent takes a token; when the bucket is empty the request waits for the next one.
+///
+/// By default requests wait as long as needed. Use [RateLimit::with_max_wait] or [RateLimit::fail_fast]
+/// to fail with [PrimaBridgeError::RateLimitExceeded] instead.
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    requests: u32,
+    interval: Duration,
+    max_wait: Option<Duration>,
+}
+
+impl RateLimit {
+    /// Allows up to `requests` requests every `interval`.
+    pub fn new(requests: u32, interval: Duration) -> Self {
+        Self {
+            requests: requests.max(1),
+            interval,
+            max_wait: None,
+        }
+    }
+
+    /// Fails the request if it would need to wait longer than `max_wait` to be sent.
+    pub fn with_max_wait(self, max_wait: Duration) -> Self {
+        Self {
+            max_wait: Some(max_wait),
+            ..self
+        }
+    }
+
+    /// Fails the request instead of waiting when the limit is reached.
+    pub fn fail_fast(self) -> Self {
+        self.with_max_wait(Duration::ZERO)
+    }
+}
+
+/// A limit on the number of requests a bridge can have in flight at the same time.
+///
+/// By default requests wait for a slot to be freed. Use [ConcurrencyLimit::with_max_wait] or
+/// [ConcurrencyLimit::fail_fast] to fail with [PrimaBridgeError::ConcurrencyLimitExceeded] instead.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimit {
+    max_concurrency: usize,
+    max_wait: Option<Duration>,
+}
+
+impl ConcurrencyLimit {
+    /// Allows up to `max_concurrency` requests in flight at the same time.
+    pub fn new(max_concurrency: usize) -> Self {
+        Self {
+            max_concurrency: max_concurrency.max(1),
+            max_wait: None,
+        }
+    }
+
+    /// Fails the request if it would need to wait longer than `max_wait` to be sent.
+    pub fn with_max_wait(self, max_wait: Duration) -> Self {
+        Self {
+            max_wait: Some(max_wait),
+            ..self
+        }
+    }
+
+    /// Fails the request instead of waiting when the limit is reached.
+    pub fn fail_fast(self) -> Self {
+        self.with_max_wait(Duration::ZERO)
+    }
+}
+
+impl From<usize> for ConcurrencyLimit {
+    fn from(max_concurrency: usize) -> Self {
+        Self::new(max_concurrency)
+    }
+}
+
+/// The token bucket enforcing a [RateLimit], shared by all the clones of a bridge.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    limit: Arc<RateLimit>,
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                tokens: limit.requests as f64,
+                refilled_at: Instant::now(),
+            })),
+            limit: Arc::new(limit),
+        }
+    }
+
+    /// Creates a limiter with the same limit, which doesn't share the bucket with this one.
+    pub(crate) fn detached(&self) -> Self {
+        Self::new(self.limit.as_ref().clone())
+    }
+
+    /// Takes a token from the bucket, waiting for it to be available if needed.
+    pub(crate) async fn acquire(&self, url: &Url) -> PrimaBridgeResult<()> {
+        let wait = self
+            .reserve()
+            .ok_or_else(|| PrimaBridgeError::RateLimitExceeded(url.clone()))?;
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    /// Reserves a token, returning how long to wait before it can be used. The token is taken in advance
+    /// so that waiting requests are served in order.
+    fn reserve(&self) -> Option<Duration> {
+        // The bucket is always left consistent, so it's fine to keep using it after a panic
+        let mut bucket = self.bucket.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let capacity = self.limit.requests as f64;
+        let tokens_per_second = capacity / self.limit.interval.as_secs_f64().max(f64::EPSILON);
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * tokens_per_second).min(capacity);
+        bucket.refilled_at = now;
+
+        let wait = if bucket.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - bucket.tokens) / tokens_per_second)
+        };
+
+        if self.limit.max_wait.is_some_and(|max_wait| wait > max_wait) {
+            return None;
+        }
+
+        bucket.tokens -= 1.0;
+        Some(wait)
+    }
+}
+
+/// The semaphore enforcing a [ConcurrencyLimit], shared by all the clones of a bridge.
+#[derive(Debug, Clone)]
+pub(crate) struct ConcurrencyLimiter {
+    limit: Arc<ConcurrencyLimit>,
+    semaphore: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimiter {
+    pub(crate) fn new(limit: ConcurrencyLimit) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit.max_concurrency)),
+            limit: Arc::new(limit),
+        }
+    }
+
+    /// Creates a limiter with the same limit, which doesn't share the slots with this one.
+    pub(crate) fn detached(&self) -> Self {
+        Self::new(self.limit.as_ref().clone())
+    }
+
+    /// Takes a slot, which is freed when the returned permit is dropped.
+    pub(crate) async fn acquire(&self, url: &Url) -> PrimaBridgeResult<OwnedSemaphorePermit> {
+        let exceeded = || PrimaBridgeError::ConcurrencyLimitExceeded(url.clone());
+        let semaphore = self.semaphore.clone();
+
+        match self.limit.max_wait {
+            None => semaphore.acquire_owned().await.map_err(|_| exceeded()),
+            Some(max_wait) if max_wait.is_zero() => semaphore.try_acquire_owned().map_err(|_| exceeded()),
+            Some(max_wait) => tokio::time::timeout(max_wait, semaphore.acquire_owned())
+                .await
+                .map_err(|_| exceeded())?
+                .map_err(|_| exceeded()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_bursts_up_to_capacity() {
+        let limiter = RateLimiter::new(RateLimit::new(3, Duration::from_secs(60)).fail_fast());
+
+        assert_eq!(limiter.reserve(), Some(Duration::ZERO));
+        assert_eq!(limiter.reserve(), Some(Duration::ZERO));
+        assert_eq!(limiter.reserve(), Some(Duration::ZERO));
+        assert_eq!(limiter.reserve(), None);
+    }
+
+    #[test]
+    fn waiting_requests_are_scheduled_one_interval_apart() {
+        let limiter = RateLimiter::new(RateLimit::new(1, Duration::from_secs(10)));
+
+        assert_eq!(limiter.reserve(), Some(Duration::ZERO));
+        let first_wait = limiter.reserve().unwrap();
+        let second_wait = limiter.reserve().unwrap();
+
+        assert!(first_wait > Duration::from_secs(9) && first_wait <= Duration::from_secs(10));
+        assert!(second_wait > Duration::from_secs(19) && second_wait <= Duration::from_secs(20));
+    }
+}
@@ -0,0 +1,269 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use reqwest::Url;
+use serde::Deserialize;
+
+use crate::errors::{PrimaBridgeError, PrimaBridgeResult};
+use crate::request::ReceivedResponse;
+
+/// How a bridge with multiple endpoints picks the endpoint of every request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancingStrategy {
+    /// Requests are spread evenly across the endpoints, in turn.
+    #[default]
+    RoundRobin,
+    /// Requests go to the first healthy endpoint, in the order they were given. When the connection to an
+    /// endpoint can't be established the endpoint is ejected, and the request is sent to the next one.
+    Failover,
+    /// Requests go to the endpoint with the fewest requests in flight.
+    LeastOutstanding,
+}
+
+/// Configures how a bridge with multiple endpoints balances the requests across them.
+///
+/// Endpoints are checked passively: after `ejection_threshold` consecutive transport errors an endpoint is
+/// ejected and receives no requests for `ejection_duration`. When every endpoint is ejected, requests are
+/// balanced across all of them anyway.
+#[derive(Debug, Clone)]
+pub struct LoadBalancingPolicy {
+    strategy: LoadBalancingStrategy,
+    ejection_threshold: u32,
+    ejection_duration: Duration,
+}
+
+impl Default for LoadBalancingPolicy {
+    fn default() -> Self {
+        Self {
+            strategy: LoadBalancingStrategy::default(),
+            ejection_threshold: 3,
+            ejection_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl LoadBalancingPolicy {
+    /// Creates a policy with the given strategy.
+    pub fn new(strategy: LoadBalancingStrategy) -> Self {
+        Self {
+            strategy,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the number of consecutive transport errors that eject an endpoint. Defaults to 3.
+    pub fn with_ejection_threshold(self, ejection_threshold: u32) -> Self {
+        Self {
+            ejection_threshold: ejection_threshold.max(1),
+            ..self
+        }
+    }
+
+    /// Sets how long an ejected endpoint receives no requests. Defaults to 30 seconds.
+    pub fn with_ejection_duration(self, ejection_duration: Duration) -> Self {
+        Self {
+            ejection_duration,
+            ..self
+        }
+    }
+}
+
+/// The endpoints of a bridge, with their health, shared by all the clones of the bridge.
+#[derive(Debug, Clone)]
+pub(crate) struct LoadBalancer {
+    policy: Arc<LoadBalancingPolicy>,
+    endpoints: Arc<[EndpointState]>,
+    next: Arc<AtomicUsize>,
+}
+
+#[derive(Debug)]
+struct EndpointState {
+    url: Url,
+    outstanding: AtomicUsize,
+    health: Mutex<Health>,
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    ejected_until: Option<Instant>,
+}
+
+impl LoadBalancer {
+    pub(crate) fn new(policy: LoadBalancingPolicy, endpoints: Vec<Url>) -> Self {
+        Self {
+            policy: Arc::new(policy),
+            endpoints: endpoints
+                .into_iter()
+                .map(|url| EndpointState {
+                    url,
+                    outstanding: AtomicUsize::new(0),
+                    health: Mutex::new(Health::default()),
+                })
+                .collect(),
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub(crate) fn endpoints(&self) -> impl Iterator<Item = &Url> {
+        self.endpoints.iter().map(|endpoint| &endpoint.url)
+    }
+
+    /// Whether requests failing to connect are sent to another endpoint.
+    pub(crate) fn fails_over(&self) -> bool {
+        self.policy.strategy == LoadBalancingStrategy::Failover && self.endpoints.len() > 1
+    }
+
+    /// Picks the endpoint of the next request, skipping the `excluded` ones unless there's nothing else left.
+    pub(crate) fn select(&self, excluded: &[usize]) -> SelectedEndpoint {
+        let now = Instant::now();
+        let count = self.endpoints.len();
+
+        let not_excluded: Vec<usize> = (0..count).filter(|index| !excluded.contains(index)).collect();
+        let healthy: Vec<usize> = not_excluded
+            .iter()
+            .copied()
+            .filter(|index| self.endpoints[*index].is_healthy(now))
+            .collect();
+        let eligible = match (healthy.is_empty(), not_excluded.is_empty()) {
+            (false, _) => healthy,
+            (true, false) => not_excluded,
+            (true, true) => (0..count).collect(),
+        };
+
+        let index = match self.policy.strategy {
+            LoadBalancingStrategy::Failover => eligible[0],
+            LoadBalancingStrategy::RoundRobin => eligible[self.next.fetch_add(1, Ordering::Relaxed) % eligible.len()],
+            LoadBalancingStrategy::LeastOutstanding => {
+                // Ties are broken in turn, to spread the requests when the bridge is idle
+                let offset = self.next.fetch_add(1, Ordering::Relaxed) % eligible.len();
+                eligible[offset..]
+                    .iter()
+                    .chain(&eligible[..offset])
+                    .copied()
+                    .min_by_key(|index| self.endpoints[*index].outstanding.load(Ordering::Relaxed))
+                    .unwrap_or(eligible[0])
+            }
+        };
+
+        self.endpoints[index].outstanding.fetch_add(1, Ordering::Relaxed);
+        SelectedEndpoint {
+            load_balancer: self.clone(),
+            index,
+        }
+    }
+}
+
+impl EndpointState {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.health().ejected_until.is_none_or(|until| now >= until)
+    }
+
+    fn health(&self) -> MutexGuard<'_, Health> {
+        // The health is always left consistent, so it's fine to keep using it after a panic
+        self.health.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// An endpoint picked by a [LoadBalancer] for a request, counted as outstanding until dropped.
+pub(crate) struct SelectedEndpoint {
+    load_balancer: LoadBalancer,
+    index: usize,
+}
+
+impl SelectedEndpoint {
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Updates the health of the endpoint with the outcome of the request sent to it.
+    pub(crate) fn record<R: ReceivedResponse>(&self, result: &PrimaBridgeResult<R>) {
+        let policy = &self.load_balancer.policy;
+        let mut health = self.load_balancer.endpoints[self.index].health();
+
+        match result {
+            Err(PrimaBridgeError::HttpError { source, .. }) => {
+                health.consecutive_failures += 1;
+                let refused = source.is_connect() && policy.strategy == LoadBalancingStrategy::Failover;
+                if refused || health.consecutive_failures >= policy.ejection_threshold {
+                    health.consecutive_failures = 0;
+                    health.ejected_until = Some(Instant::now() + policy.ejection_duration);
+                }
+            }
+            _ => {
+                health.consecutive_failures = 0;
+                health.ejected_until = None;
+            }
+        }
+    }
+}
+
+impl Drop for SelectedEndpoint {
+    fn drop(&mut self) {
+        self.load_balancer.endpoints[self.index]
+            .outstanding
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_balancer(policy: LoadBalancingPolicy) -> LoadBalancer {
+        LoadBalancer::new(
+            policy,
+            vec![
+                Url::parse("http://one.prima.it").unwrap(),
+                Url::parse("http://two.prima.it").unwrap(),
+                Url::parse("http://three.prima.it").unwrap(),
+            ],
+        )
+    }
+
+    fn eject(endpoint: &SelectedEndpoint) {
+        endpoint.load_balancer.endpoints[endpoint.index].health().ejected_until =
+            Some(Instant::now() + Duration::from_secs(60));
+    }
+
+    #[test]
+    fn round_robin_skips_ejected_endpoints() {
+        let load_balancer = load_balancer(LoadBalancingPolicy::new(LoadBalancingStrategy::RoundRobin));
+
+        let picks: Vec<usize> = (0..3).map(|_| load_balancer.select(&[]).index()).collect();
+        assert_eq!(picks, vec![0, 1, 2]);
+
+        eject(&load_balancer.select(&[]));
+        let picks: Vec<usize> = (0..4).map(|_| load_balancer.select(&[]).index()).collect();
+        assert_eq!(picks, vec![1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn failover_prefers_endpoints_in_order() {
+        let load_balancer = load_balancer(LoadBalancingPolicy::new(LoadBalancingStrategy::Failover));
+
+        assert_eq!(load_balancer.select(&[]).index(), 0);
+        assert_eq!(load_balancer.select(&[0]).index(), 1);
+        assert_eq!(load_balancer.select(&[0, 1, 2]).index(), 0);
+
+        eject(&load_balancer.select(&[]));
+        assert_eq!(load_balancer.select(&[]).index(), 1);
+    }
+
+    #[test]
+    fn least_outstanding_picks_the_least_busy_endpoint() {
+        let load_balancer = load_balancer(LoadBalancingPolicy::new(LoadBalancingStrategy::LeastOutstanding));
+
+        let first = load_balancer.select(&[]);
+        let second = load_balancer.select(&[]);
+        let third = load_balancer.select(&[]);
+        assert_ne!(first.index(), second.index());
+        assert_ne!(second.index(), third.index());
+
+        let freed = second.index();
+        drop(second);
+        assert_eq!(load_balancer.select(&[]).index(), freed);
+    }
+}
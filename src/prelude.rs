@@ -1,6 +1,6 @@
 pub use super::errors::*;
 pub use super::{
     request::{DeliverableRequest, GraphQLRequest, Request, RestRequest},
-    response::Response,
+    response::{Response, StreamingResponse},
     Bridge,
 };
@@ -0,0 +1,106 @@
+use reqwest::Url;
+use serde::Deserialize;
+
+/// The requests routed through a [Proxy], by the scheme of their URL.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyScope {
+    /// Both HTTP and HTTPS requests.
+    #[default]
+    All,
+    /// HTTP requests only.
+    Http,
+    /// HTTPS requests only.
+    Https,
+}
+
+/// An outbound proxy the requests of a bridge are sent through.
+///
+/// Unless a proxy is set, or proxies are disabled with
+/// [without_proxies](crate::builder::BridgeBuilderInner::without_proxies), the bridge uses the proxies set by
+/// the `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables.
+#[derive(Clone)]
+pub struct Proxy {
+    url: Url,
+    scope: ProxyScope,
+    basic_auth: Option<(String, String)>,
+    no_proxy: Vec<String>,
+}
+
+// The password is redacted, so that it doesn't end up in the logs
+impl std::fmt::Debug for Proxy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Proxy")
+            .field("url", &self.url)
+            .field("scope", &self.scope)
+            .field(
+                "basic_auth",
+                &self.basic_auth.as_ref().map(|(username, _)| (username, "<redacted>")),
+            )
+            .field("no_proxy", &self.no_proxy)
+            .finish()
+    }
+}
+
+impl Proxy {
+    /// Creates a proxy for the requests of the given scope.
+    pub fn new(url: Url, scope: ProxyScope) -> Self {
+        Self {
+            url,
+            scope,
+            basic_auth: None,
+            no_proxy: Vec::new(),
+        }
+    }
+
+    /// Creates a proxy for both HTTP and HTTPS requests.
+    pub fn all(url: Url) -> Self {
+        Self::new(url, ProxyScope::All)
+    }
+
+    /// Creates a proxy for HTTP requests.
+    pub fn http(url: Url) -> Self {
+        Self::new(url, ProxyScope::Http)
+    }
+
+    /// Creates a proxy for HTTPS requests.
+    pub fn https(url: Url) -> Self {
+        Self::new(url, ProxyScope::Https)
+    }
+
+    /// Authenticates to the proxy with the given credentials.
+    pub fn with_basic_auth(self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            basic_auth: Some((username.into(), password.into())),
+            ..self
+        }
+    }
+
+    /// Sends the requests to the given hosts directly, bypassing the proxy, like the `NO_PROXY` environment
+    /// variable does.
+    ///
+    /// Every entry is a host name, matching its subdomains too, an IP address or a CIDR block, eg.
+    /// `"prima.it"`, `"127.0.0.1"` or `"10.0.0.0/8"`. The `"*"` entry bypasses the proxy for every host.
+    pub fn with_no_proxy<I, S>(self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut no_proxy = self.no_proxy;
+        no_proxy.extend(hosts.into_iter().map(Into::into));
+        Self { no_proxy, ..self }
+    }
+
+    pub(crate) fn into_reqwest(self) -> reqwest::Result<reqwest::Proxy> {
+        let proxy = match self.scope {
+            ProxyScope::All => reqwest::Proxy::all(self.url)?,
+            ProxyScope::Http => reqwest::Proxy::http(self.url)?,
+            ProxyScope::Https => reqwest::Proxy::https(self.url)?,
+        };
+        let proxy = match &self.basic_auth {
+            Some((username, password)) => proxy.basic_auth(username, password),
+            None => proxy,
+        };
+        Ok(proxy.no_proxy(reqwest::NoProxy::from_string(&self.no_proxy.join(","))))
+    }
+}
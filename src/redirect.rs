@@ -1,6 +1,9 @@
 use reqwest::redirect::Policy as ReqwestPolicy;
+use serde::Deserialize;
 
 /// Determines how to handle HTTP redirects (3xx responses).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RedirectPolicy {
     /// Don't follow redirects. Return an error in case of a redirect response.
     NoFollow,
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::multipart::Part;
 use serde::Serialize;
 
@@ -40,6 +41,13 @@ impl Body {
         self.inner.as_bytes()
     }
 
+    /// Clones the body, if it is in memory.
+    pub fn try_clone(&self) -> Option<Body> {
+        self.as_bytes().map(|bytes| Self {
+            inner: bytes.to_vec().into(),
+        })
+    }
+
     #[cfg(test)]
     pub(crate) fn as_str(&self) -> Option<Cow<'_, str>> {
         self.inner.as_bytes().map(String::from_utf8_lossy)
@@ -143,6 +151,14 @@ impl MultipartFile {
         }
     }
 
+    pub(crate) fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            content: self.content.try_clone()?,
+            name_opt: self.name_opt.clone(),
+            mime_type_opt: self.mime_type_opt.clone(),
+        })
+    }
+
     pub(crate) fn into_part(self) -> PrimaBridgeResult<Part> {
         let mut part = Part::stream(self.content.inner);
         if let Some(name) = self.name_opt {
@@ -173,4 +189,103 @@ impl MultipartFormFileField {
             file,
         }
     }
+
+    pub(crate) fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            field_name: self.field_name.clone(),
+            file: self.file.try_clone()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+enum MultipartPartContent {
+    File(MultipartFile),
+    Text(String),
+    Json(Vec<u8>),
+}
+
+#[derive(Debug)]
+/// A named multipart-form field, which contains either a file, a text value or a JSON document, along with the
+/// headers of its part.
+pub struct MultipartPart {
+    pub(crate) field_name: Cow<'static, str>,
+    content: MultipartPartContent,
+    headers: HeaderMap,
+}
+
+impl MultipartPart {
+    /// Creates a part containing a file.
+    pub fn file<S>(field_name: S, file: MultipartFile) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::new(field_name, MultipartPartContent::File(file))
+    }
+
+    /// Creates a part containing a plain text value, like a form input.
+    pub fn text<S>(field_name: S, value: impl Into<String>) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::new(field_name, MultipartPartContent::Text(value.into()))
+    }
+
+    /// Creates a part containing the given value serialized as JSON, with the `application/json` content type.
+    pub fn json<S, T>(field_name: S, value: &T) -> PrimaBridgeResult<Self>
+    where
+        S: Into<Cow<'static, str>>,
+        T: Serialize + ?Sized,
+    {
+        Ok(Self::new(
+            field_name,
+            MultipartPartContent::Json(serde_json::to_vec(value)?),
+        ))
+    }
+
+    /// Adds a header to the part, eg. a `content-id` or a checksum.
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    fn new<S>(field_name: S, content: MultipartPartContent) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self {
+            field_name: field_name.into(),
+            content,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    pub(crate) fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            field_name: self.field_name.clone(),
+            content: match &self.content {
+                MultipartPartContent::File(file) => MultipartPartContent::File(file.try_clone()?),
+                MultipartPartContent::Text(value) => MultipartPartContent::Text(value.clone()),
+                MultipartPartContent::Json(value) => MultipartPartContent::Json(value.clone()),
+            },
+            headers: self.headers.clone(),
+        })
+    }
+
+    pub(crate) fn into_part(self) -> PrimaBridgeResult<Part> {
+        let part = match self.content {
+            MultipartPartContent::File(file) => file.into_part()?,
+            MultipartPartContent::Text(value) => Part::text(value),
+            MultipartPartContent::Json(value) => Part::bytes(value)
+                .mime_str("application/json")
+                .map_err(|_| PrimaBridgeError::InvalidMultipartFileMimeType("application/json".to_string()))?,
+        };
+        Ok(part.headers(self.headers))
+    }
+}
+
+impl From<MultipartFormFileField> for MultipartPart {
+    fn from(field: MultipartFormFileField) -> Self {
+        Self::file(field.field_name, field.file)
+    }
 }
@@ -0,0 +1,506 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use reqwest::header::{HeaderMap, LINK};
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tracing::Instrument;
+
+use crate::errors::{PrimaBridgeError, PrimaBridgeResult};
+use crate::request::{DeliverableRequest, RestRequest};
+use crate::response::extract_inner_json;
+use crate::BridgeClient;
+
+/// The items of all the pages of a [paginated](crate::RestRequest::paginate) request.
+pub type PaginatedStream<T> = Pin<Box<dyn Stream<Item = PrimaBridgeResult<T>> + Send>>;
+
+/// How to request the pages of a list endpoint, and where to find their items, to be used with
+/// [RestRequest::paginate](crate::RestRequest::paginate).
+///
+/// The items are read from the top level JSON array of every page, unless set with
+/// [with_items](Self::with_items).
+#[derive(Debug, Clone)]
+pub struct Pagination {
+    scheme: Scheme,
+    items: Vec<String>,
+    max_pages: Option<usize>,
+    prefetch: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Scheme {
+    LinkHeader,
+    Cursor {
+        selectors: Vec<String>,
+        param: String,
+    },
+    Offset {
+        offset_param: String,
+        limit_param: String,
+        limit: u64,
+    },
+    PageNumber {
+        param: String,
+        first_page: u64,
+        page_size: u64,
+    },
+}
+
+impl Pagination {
+    fn new(scheme: Scheme) -> Self {
+        Self {
+            scheme,
+            items: vec![],
+            max_pages: None,
+            prefetch: 0,
+        }
+    }
+
+    /// Follows the `rel="next"` URL of the `Link` header of every page, until there is none.
+    ///
+    /// The URL has to be on the endpoint of the bridge, as the requests carry its credentials.
+    pub fn link_header() -> Self {
+        Self::new(Scheme::LinkHeader)
+    }
+
+    /// Sends the cursor found in every page by following the given keys, like
+    /// [get_data](crate::Response::get_data) does, as the `param` query parameter of the next request, until
+    /// it's missing, `null` or empty.
+    pub fn cursor(selectors: &[&str], param: impl Into<String>) -> Self {
+        Self::new(Scheme::Cursor {
+            selectors: selectors.iter().map(|selector| selector.to_string()).collect(),
+            param: param.into(),
+        })
+    }
+
+    /// Sends the `offset_param` and `limit_param` query parameters, starting from offset 0, until a page has
+    /// less than `limit` items.
+    pub fn offset(offset_param: impl Into<String>, limit_param: impl Into<String>, limit: u64) -> Self {
+        Self::new(Scheme::Offset {
+            offset_param: offset_param.into(),
+            limit_param: limit_param.into(),
+            limit,
+        })
+    }
+
+    /// Sends the number of the page as the `param` query parameter, starting from `first_page`, until a page
+    /// has less than `page_size` items, the number of items of a full page.
+    pub fn page_number(param: impl Into<String>, first_page: u64, page_size: u64) -> Self {
+        Self::new(Scheme::PageNumber {
+            param: param.into(),
+            first_page,
+            page_size,
+        })
+    }
+
+    /// Reads the items of every page from the array found by following the given keys, like
+    /// [get_data](crate::Response::get_data) does.
+    pub fn with_items(self, selectors: &[&str]) -> Self {
+        Self {
+            items: selectors.iter().map(|selector| selector.to_string()).collect(),
+            ..self
+        }
+    }
+
+    /// Stops after the given number of pages, even if there are more.
+    pub fn with_max_pages(self, max_pages: usize) -> Self {
+        Self {
+            max_pages: Some(max_pages),
+            ..self
+        }
+    }
+
+    /// Requests up to the given number of pages ahead of the ones being consumed, from a spawned task.
+    ///
+    /// By default, a page is requested only once all the items of the previous one have been consumed.
+    pub fn with_prefetch(self, pages: usize) -> Self {
+        Self {
+            prefetch: pages,
+            ..self
+        }
+    }
+}
+
+struct Pages<Client: BridgeClient + 'static, T> {
+    pagination: Pagination,
+    /// The request of the next page, or the reason why it couldn't be made.
+    next: Option<PrimaBridgeResult<RestRequest<'static, Client>>>,
+    fetched: usize,
+    _items: PhantomData<fn() -> T>,
+}
+
+impl<Client, T> Pages<Client, T>
+where
+    Client: BridgeClient + Send + Sync + 'static,
+    T: DeserializeOwned + Send + 'static,
+{
+    /// Sends the request of a page, returning its items and the request of the next one, if any.
+    async fn fetch(
+        &self,
+        request: RestRequest<'static, Client>,
+    ) -> PrimaBridgeResult<(Vec<T>, Option<PrimaBridgeResult<RestRequest<'static, Client>>>)> {
+        let url = request.get_url();
+        let template = request.try_clone();
+        let response = request.send().await?;
+        let next_link = next_link(response.headers());
+        let body: Value = response.get_data(&[])?;
+
+        let cursor = match &self.pagination.scheme {
+            Scheme::Cursor { selectors, .. } => selectors
+                .iter()
+                .try_fold(&body, |value, selector| value.get(selector))
+                .and_then(cursor_value),
+            _ => None,
+        };
+        let items: Vec<T> = extract_inner_json(
+            url.clone(),
+            self.pagination.items.iter().map(String::as_str).collect(),
+            body,
+        )?;
+
+        let has_next = match &self.pagination.scheme {
+            Scheme::LinkHeader => next_link.is_some(),
+            Scheme::Cursor { .. } => cursor.is_some(),
+            Scheme::Offset { limit, .. } => items.len() as u64 >= *limit && *limit > 0,
+            Scheme::PageNumber { page_size, .. } => items.len() as u64 >= *page_size && *page_size > 0,
+        };
+        if !has_next {
+            return Ok((items, None));
+        }
+        Ok((items, Some(self.next_request(url, template, next_link, cursor))))
+    }
+
+    fn next_request(
+        &self,
+        url: Url,
+        template: Option<RestRequest<'static, Client>>,
+        next_link: Option<String>,
+        cursor: Option<String>,
+    ) -> PrimaBridgeResult<RestRequest<'static, Client>> {
+        let error = |reason: &str| PrimaBridgeError::PaginationError {
+            url: url.clone(),
+            reason: reason.to_string(),
+        };
+        let mut next = template.ok_or_else(|| error("the request body can't be cloned"))?;
+
+        match &self.pagination.scheme {
+            Scheme::LinkHeader => {
+                let link = next_link.unwrap_or_default();
+                let link = url
+                    .join(&link)
+                    .map_err(|_| error(&format!("invalid next link `{link}`")))?;
+                let path = relative_path(&next.endpoint(), &link)
+                    .ok_or_else(|| error(&format!("the next link `{link}` is not on the endpoint of the bridge")))?;
+                let route = next.get_route_mut().take();
+                next = next.to(path);
+                *next.get_route_mut() = route;
+                *next.get_query_pairs_mut() = link
+                    .query_pairs()
+                    .map(|(name, value)| (Cow::Owned(name.into_owned()), Cow::Owned(value.into_owned())))
+                    .collect();
+            }
+            Scheme::Cursor { param, .. } => set_query_pair(&mut next, param, cursor.unwrap_or_default()),
+            Scheme::Offset {
+                offset_param, limit, ..
+            } => {
+                let offset = query_pair(&next, offset_param).unwrap_or(0);
+                set_query_pair(&mut next, offset_param, (offset + limit).to_string());
+            }
+            Scheme::PageNumber { param, first_page, .. } => {
+                let page = query_pair(&next, param).unwrap_or(*first_page);
+                set_query_pair(&mut next, param, (page + 1).to_string());
+            }
+        }
+
+        Ok(next)
+    }
+}
+
+/// Returns the items of all the pages of the given request, see [RestRequest::paginate].
+pub(crate) fn paginate<Client, T>(
+    mut request: RestRequest<'static, Client>,
+    pagination: Pagination,
+) -> PaginatedStream<T>
+where
+    Client: BridgeClient + Send + Sync + 'static,
+    T: DeserializeOwned + Send + 'static,
+{
+    match &pagination.scheme {
+        Scheme::LinkHeader | Scheme::Cursor { .. } => {}
+        Scheme::Offset {
+            offset_param,
+            limit_param,
+            limit,
+        } => {
+            set_query_pair(&mut request, offset_param, "0".to_string());
+            set_query_pair(&mut request, limit_param, limit.to_string());
+        }
+        Scheme::PageNumber { param, first_page, .. } => set_query_pair(&mut request, param, first_page.to_string()),
+    }
+
+    let prefetch = pagination.prefetch;
+    let pages = Pages {
+        pagination,
+        next: Some(Ok(request)),
+        fetched: 0,
+        _items: PhantomData,
+    };
+    let pages = futures::stream::unfold(pages, |mut pages| async move {
+        if pages
+            .pagination
+            .max_pages
+            .is_some_and(|max_pages| pages.fetched >= max_pages)
+        {
+            return None;
+        }
+        let request = match pages.next.take()? {
+            Ok(request) => request,
+            Err(error) => return Some((Err(error), pages)),
+        };
+        pages.fetched += 1;
+        match pages.fetch(request).await {
+            Ok((items, next)) => {
+                pages.next = next;
+                Some((Ok(items), pages))
+            }
+            Err(error) => Some((Err(error), pages)),
+        }
+    });
+
+    let pages: Pin<Box<dyn Stream<Item = PrimaBridgeResult<Vec<T>>> + Send>> = if prefetch == 0 {
+        Box::pin(pages)
+    } else {
+        let (sender, receiver) = tokio::sync::mpsc::channel(prefetch);
+        tokio::spawn(
+            async move {
+                let mut pages = Box::pin(pages);
+                while let Some(page) = pages.next().await {
+                    // The stream has been dropped
+                    if sender.send(page).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            .instrument(tracing::Span::current()),
+        );
+        Box::pin(futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|page| (page, receiver))
+        }))
+    };
+
+    Box::pin(pages.flat_map(|page| {
+        futures::stream::iter(match page {
+            Ok(items) => items.into_iter().map(Ok).collect(),
+            Err(error) => vec![Err(error)],
+        })
+    }))
+}
+
+fn cursor_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(cursor) if !cursor.is_empty() => Some(cursor.clone()),
+        Value::Number(cursor) => Some(cursor.to_string()),
+        _ => None,
+    }
+}
+
+fn query_pair<Client: BridgeClient>(request: &RestRequest<'static, Client>, name: &str) -> Option<u64> {
+    request
+        .get_query_pairs()
+        .iter()
+        .rev()
+        .find(|(pair_name, _)| pair_name == name)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+fn set_query_pair<Client: BridgeClient>(request: &mut RestRequest<'static, Client>, name: &str, value: String) {
+    let query_pairs = request.get_query_pairs_mut();
+    query_pairs.retain(|(pair_name, _)| pair_name != name);
+    query_pairs.push((Cow::Owned(name.to_string()), Cow::Owned(value)));
+}
+
+/// Returns the URL with `rel="next"` in the `Link` headers, as specified by RFC 8288.
+fn next_link(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(parse_links)
+        .find(|link| {
+            // Only the first `rel` parameter counts, and it can hold many relation types
+            link.params
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("rel"))
+                .is_some_and(|(_, rel)| rel.split_ascii_whitespace().any(|rel| rel.eq_ignore_ascii_case("next")))
+        })
+        .map(|link| link.target.to_string())
+}
+
+/// A link of a `Link` header, with the values of its parameters unquoted.
+#[derive(Debug, PartialEq)]
+struct Link<'a> {
+    target: &'a str,
+    params: Vec<(&'a str, String)>,
+}
+
+/// Parses the comma separated links of a `Link` header, `<uri>; name=value; name="quoted value"`, up to the
+/// first malformed one.
+fn parse_links(value: &str) -> Vec<Link<'_>> {
+    let is_delimiter = |c: char| c == ';' || c == ',' || c == '=' || c.is_ascii_whitespace();
+    let mut links = vec![];
+    let mut rest = value;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        let Some((target, after_target)) = rest.strip_prefix('<').and_then(|rest| rest.split_once('>')) else {
+            return links;
+        };
+        rest = after_target;
+
+        let mut params = vec![];
+        while let Some(param) = rest.trim_start().strip_prefix(';') {
+            let param = param.trim_start();
+            let name_end = param.find(is_delimiter).unwrap_or(param.len());
+            let (name, after_name) = param.split_at(name_end);
+            rest = after_name.trim_start();
+
+            let mut value = String::new();
+            if let Some(after_equals) = rest.strip_prefix('=') {
+                rest = after_equals.trim_start();
+                rest = match rest.strip_prefix('"') {
+                    Some(quoted) => unquote(quoted, &mut value),
+                    None => {
+                        let value_end = rest.find(is_delimiter).unwrap_or(rest.len());
+                        value.push_str(&rest[..value_end]);
+                        &rest[value_end..]
+                    }
+                };
+            }
+            if !name.is_empty() {
+                params.push((name, value));
+            }
+        }
+        links.push(Link { target, params });
+
+        // Skips whatever follows the parameters, up to the next link
+        match rest.find(',') {
+            Some(comma) => rest = &rest[comma..],
+            None => return links,
+        }
+    }
+}
+
+/// Reads the quoted string starting after the opening quote into `value`, returning what follows the closing
+/// one.
+fn unquote<'a>(quoted: &'a str, value: &mut String) -> &'a str {
+    let mut chars = quoted.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return &quoted[index + 1..],
+            '\\' => value.extend(chars.next().map(|(_, escaped)| escaped)),
+            c => value.push(c),
+        }
+    }
+    ""
+}
+
+/// Returns the path of the URL relative to the one of the endpoint, if it's on the endpoint.
+fn relative_path(endpoint: &Url, url: &Url) -> Option<String> {
+    if endpoint.origin() != url.origin() {
+        return None;
+    }
+    let base: Vec<&str> = endpoint
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let segments: Vec<&str> = url.path_segments()?.collect();
+    if segments.len() < base.len() || segments[..base.len()] != base[..] {
+        return None;
+    }
+    Some(segments[base.len()..].join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn finds_the_next_link() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            LINK,
+            HeaderValue::from_static(r#"<https://api.example.com/items?page=1>; rel="prev first""#),
+        );
+        headers.append(
+            LINK,
+            HeaderValue::from_static(
+                r#"<https://api.example.com/items?page=3>; title="a;b", <items?page=2>; REL=next"#,
+            ),
+        );
+
+        assert_eq!(next_link(&headers), Some("items?page=2".to_string()));
+        assert_eq!(next_link(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn finds_the_next_link_among_many_relation_types() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            LINK,
+            HeaderValue::from_static(
+                r#"<items?page=9>; title="rel=next, <x>"; rel="last", <items?page=2>; rel="next last"; rel=prev"#,
+            ),
+        );
+
+        assert_eq!(next_link(&headers), Some("items?page=2".to_string()));
+    }
+
+    #[test]
+    fn parses_the_links_and_their_parameters() {
+        assert_eq!(
+            parse_links(r#"<a,b>; rel = "next \"quoted\"";anchor=x, <c>;hreflang ; rel=prev  "#),
+            vec![
+                Link {
+                    target: "a,b",
+                    params: vec![("rel", r#"next "quoted""#.to_string()), ("anchor", "x".to_string())],
+                },
+                Link {
+                    target: "c",
+                    params: vec![("hreflang", String::new()), ("rel", "prev".to_string())],
+                },
+            ]
+        );
+        assert_eq!(
+            parse_links("<a>; rel=next, garbage, <b>"),
+            vec![Link {
+                target: "a",
+                params: vec![("rel", "next".to_string())]
+            }]
+        );
+    }
+
+    #[test]
+    fn resolves_the_path_relative_to_the_endpoint() {
+        let endpoint = Url::parse("https://api.example.com/v1/").unwrap();
+
+        assert_eq!(
+            relative_path(
+                &endpoint,
+                &Url::parse("https://api.example.com/v1/items/all?page=2").unwrap()
+            ),
+            Some("items/all".to_string())
+        );
+        assert_eq!(
+            relative_path(&endpoint, &Url::parse("https://api.example.com/v2/items").unwrap()),
+            None
+        );
+        assert_eq!(
+            relative_path(&endpoint, &Url::parse("https://evil.example.com/v1/items").unwrap()),
+            None
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::{HashMap, VecDeque};
 use std::time::Duration;
 
@@ -11,7 +12,8 @@ use uuid::Uuid;
 
 use crate::errors::{PrimaBridgeError, PrimaBridgeResult};
 use crate::request::{Body, DeliverableRequest, DeliverableRequestBody, GraphQLBody, RequestType};
-use crate::{BridgeClient, BridgeImpl, MultipartFile};
+use crate::sealed::SealedRequest;
+use crate::{BridgeClient, BridgeImpl, MultipartFile, RetryPolicy};
 
 const VARIABLES: &str = "variables";
 const ZERO: &str = "0";
@@ -20,15 +22,18 @@ const ZERO: &str = "0";
 #[allow(clippy::upper_case_acronyms)]
 pub struct GraphQLRequest<'a, Client: BridgeClient> {
     id: Uuid,
-    bridge: &'a BridgeImpl<Client>,
+    bridge: Cow<'a, BridgeImpl<Client>>,
     body: Body,
     method: Method,
-    timeout: Duration,
-    path: Option<&'a str>,
-    query_pairs: Vec<(&'a str, &'a str)>,
+    timeout: Option<Duration>,
+    path: Option<Cow<'a, str>>,
+    route: Option<Cow<'a, str>>,
+    query_pairs: Vec<(Cow<'a, str>, Cow<'a, str>)>,
     ignore_status_code: bool,
     custom_headers: HeaderMap,
     multipart: Option<GraphQLMultipart>,
+    retry_policy: Option<RetryPolicy>,
+    max_response_bytes: Option<u64>,
 }
 
 impl<'a, Client: BridgeClient> GraphQLRequest<'a, Client> {
@@ -41,15 +46,18 @@ impl<'a, Client: BridgeClient> GraphQLRequest<'a, Client> {
         custom_headers.append(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         Ok(Self {
             id: Uuid::new_v4(),
-            bridge,
+            bridge: Cow::Borrowed(bridge),
             body: serde_json::to_string(&graphql_body.into())?.into(),
             method: Method::POST,
             path: Default::default(),
-            timeout: Duration::from_secs(60),
+            route: Default::default(),
+            timeout: None,
             query_pairs: Default::default(),
             ignore_status_code: Default::default(),
             custom_headers,
             multipart: None,
+            retry_policy: None,
+            max_response_bytes: None,
         })
     }
 
@@ -79,17 +87,72 @@ impl<'a, Client: BridgeClient> GraphQLRequest<'a, Client> {
 
         Ok(Self {
             id: Uuid::new_v4(),
-            bridge,
+            bridge: Cow::Borrowed(bridge),
             body: serde_json::to_string(&body_with_injected_variables)?.into(),
             method: Method::POST,
             path: Default::default(),
-            timeout: Duration::from_secs(60),
+            route: Default::default(),
+            timeout: None,
             query_pairs: Default::default(),
             ignore_status_code: Default::default(),
             custom_headers: HeaderMap::new(),
             multipart: Some(multipart),
+            retry_policy: None,
+            max_response_bytes: None,
         })
     }
+
+    /// Converts the request into one that owns all its data, including a clone of the bridge, so that it can
+    /// be stored or sent from a spawned task.
+    pub fn into_owned(self) -> GraphQLRequest<'static, Client> {
+        GraphQLRequest {
+            id: self.id,
+            bridge: Cow::Owned(self.bridge.into_owned()),
+            body: self.body,
+            method: self.method,
+            timeout: self.timeout,
+            path: self.path.map(|path| Cow::Owned(path.into_owned())),
+            route: self.route.map(|route| Cow::Owned(route.into_owned())),
+            query_pairs: self
+                .query_pairs
+                .into_iter()
+                .map(|(name, value)| (Cow::Owned(name.into_owned()), Cow::Owned(value.into_owned())))
+                .collect(),
+            ignore_status_code: self.ignore_status_code,
+            custom_headers: self.custom_headers,
+            multipart: self.multipart,
+            retry_policy: self.retry_policy,
+            max_response_bytes: self.max_response_bytes,
+        }
+    }
+
+    /// Clones the request, if its body and files are in memory. The clone gets a new id.
+    pub fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            id: Uuid::new_v4(),
+            bridge: self.bridge.clone(),
+            body: self.body.try_clone()?,
+            method: self.method.clone(),
+            timeout: self.timeout,
+            path: self.path.clone(),
+            route: self.route.clone(),
+            query_pairs: self.query_pairs.clone(),
+            ignore_status_code: self.ignore_status_code,
+            custom_headers: self.custom_headers.clone(),
+            multipart: match &self.multipart {
+                Some(multipart) => Some(multipart.try_clone()?),
+                None => None,
+            },
+            retry_policy: self.retry_policy.clone(),
+            max_response_bytes: self.max_response_bytes,
+        })
+    }
+}
+
+impl<Client: BridgeClient> SealedRequest for GraphQLRequest<'_, Client> {
+    fn request_timeout(&self) -> Option<Duration> {
+        Some(self.get_timeout())
+    }
 }
 
 #[async_trait]
@@ -113,9 +176,10 @@ impl<'a, Client: BridgeClient> DeliverableRequest<'a> for GraphQLRequest<'a, Cli
         Self { method, ..self }
     }
 
-    fn to(self, path: &'a str) -> Self {
+    fn to(self, path: impl Into<Cow<'a, str>>) -> Self {
         Self {
-            path: Some(path),
+            path: Some(path.into()),
+            route: None,
             ..self
         }
     }
@@ -128,11 +192,14 @@ impl<'a, Client: BridgeClient> DeliverableRequest<'a> for GraphQLRequest<'a, Cli
     }
 
     fn set_timeout(self, timeout: Duration) -> Self {
-        Self { timeout, ..self }
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
     }
 
     fn get_timeout(&self) -> Duration {
-        self.timeout
+        self.timeout.unwrap_or(self.bridge.default_timeout)
     }
 
     fn get_id(&self) -> Uuid {
@@ -140,22 +207,30 @@ impl<'a, Client: BridgeClient> DeliverableRequest<'a> for GraphQLRequest<'a, Cli
     }
 
     fn get_bridge(&self) -> &BridgeImpl<Client> {
-        self.bridge
+        &self.bridge
     }
 
     fn get_path(&self) -> Option<&str> {
-        self.path
+        self.path.as_deref()
+    }
+
+    fn get_route(&self) -> Option<&str> {
+        self.route.as_deref()
+    }
+
+    fn get_route_mut(&mut self) -> &mut Option<Cow<'a, str>> {
+        &mut self.route
     }
 
     fn endpoint(&self) -> Url {
         self.bridge.endpoint.clone()
     }
 
-    fn get_query_pairs(&self) -> &[(&'a str, &'a str)] {
+    fn get_query_pairs(&self) -> &[(Cow<'a, str>, Cow<'a, str>)] {
         self.query_pairs.as_slice()
     }
 
-    fn get_query_pairs_mut(&mut self) -> &mut Vec<(&'a str, &'a str)> {
+    fn get_query_pairs_mut(&mut self) -> &mut Vec<(Cow<'a, str>, Cow<'a, str>)> {
         &mut self.query_pairs
     }
 
@@ -175,6 +250,22 @@ impl<'a, Client: BridgeClient> DeliverableRequest<'a> for GraphQLRequest<'a, Cli
         &mut self.custom_headers
     }
 
+    fn get_retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    fn get_retry_policy_mut(&mut self) -> &mut Option<RetryPolicy> {
+        &mut self.retry_policy
+    }
+
+    fn get_max_response_bytes(&self) -> Option<u64> {
+        self.max_response_bytes
+    }
+
+    fn get_max_response_bytes_mut(&mut self) -> &mut Option<u64> {
+        &mut self.max_response_bytes
+    }
+
     #[cfg(feature = "auth0")]
     fn get_auth0(&self) -> &Option<crate::auth0::RefreshingToken> {
         &self.bridge.auth0_opt
@@ -226,6 +317,27 @@ impl GraphQLMultipart {
         Self::Multiple(Multiple::from_map(map))
     }
 
+    fn try_clone(&self) -> Option<Self> {
+        Some(match self {
+            Self::Single(single) => Self::Single(Single {
+                path: single.path.clone(),
+                file: single.file.try_clone()?,
+            }),
+            Self::Multiple(multiple) => Self::Multiple(Multiple {
+                map: multiple
+                    .map
+                    .iter()
+                    .map(|(path, files)| {
+                        Some((
+                            path.clone(),
+                            files.iter().map(MultipartFile::try_clone).collect::<Option<_>>()?,
+                        ))
+                    })
+                    .collect::<Option<_>>()?,
+            }),
+        })
+    }
+
     pub(crate) fn into_form(self, body: Body) -> PrimaBridgeResult<Form> {
         let mut form: Form = Form::new();
         form = form.part("operations", Part::stream(body.inner));
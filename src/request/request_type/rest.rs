@@ -3,30 +3,43 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use reqwest::{
-    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    header::{HeaderMap, HeaderValue, ACCEPT, CACHE_CONTROL, CONTENT_TYPE},
     multipart::Form,
     Method, Url,
 };
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use uuid::Uuid;
 
 use crate::errors::PrimaBridgeResult;
-use crate::request::{Body, DeliverableRequest, DeliverableRequestBody, MultipartFormFileField, RequestType};
-use crate::{BridgeClient, BridgeImpl, MultipartFile};
+use crate::idempotency::{self, IdempotencyKey};
+use crate::request::{
+    pagination, Body, DeliverableRequest, DeliverableRequestBody, MultipartFormFileField, MultipartPart,
+    PaginatedStream, Pagination, RequestType,
+};
+use crate::response::{event_stream, EventStream};
+use crate::sealed::SealedRequest;
+use crate::{BridgeClient, BridgeImpl, MultipartFile, RetryPolicy};
 
 /// The RestRequest is a struct that represent a REST request to be done with a [crate::Bridge].
 #[derive(Debug)]
 pub struct RestRequest<'a, Client: BridgeClient> {
     id: Uuid,
-    bridge: &'a BridgeImpl<Client>,
+    bridge: Cow<'a, BridgeImpl<Client>>,
     body: Option<Body>,
     method: Method,
-    timeout: Duration,
-    path: Option<&'a str>,
-    query_pairs: Vec<(&'a str, &'a str)>,
+    timeout: Option<Duration>,
+    /// Whether the request is timed out after the default timeout of the bridge, when it doesn't set its own.
+    default_timeout: bool,
+    path: Option<Cow<'a, str>>,
+    route: Option<Cow<'a, str>>,
+    query_pairs: Vec<(Cow<'a, str>, Cow<'a, str>)>,
     ignore_status_code: bool,
     custom_headers: HeaderMap,
     multipart: Option<RestMultipart>,
+    retry_policy: Option<RetryPolicy>,
+    max_response_bytes: Option<u64>,
+    idempotency_key: Option<IdempotencyKey>,
 }
 
 impl<'a, Client: BridgeClient> RestRequest<'a, Client> {
@@ -36,15 +49,20 @@ impl<'a, Client: BridgeClient> RestRequest<'a, Client> {
     pub fn new(bridge: &'a BridgeImpl<Client>) -> Self {
         Self {
             id: Uuid::new_v4(),
-            bridge,
+            bridge: Cow::Borrowed(bridge),
             body: Default::default(),
             method: Method::GET,
             path: Default::default(),
-            timeout: Duration::from_secs(60),
+            route: Default::default(),
+            timeout: None,
+            default_timeout: true,
             query_pairs: Default::default(),
             ignore_status_code: Default::default(),
             custom_headers: Default::default(),
             multipart: Default::default(),
+            retry_policy: Default::default(),
+            max_response_bytes: Default::default(),
+            idempotency_key: Default::default(),
         }
     }
 
@@ -55,6 +73,131 @@ impl<'a, Client: BridgeClient> RestRequest<'a, Client> {
             ..self
         }
     }
+
+    /// Sends an `Idempotency-Key` header generated from the id of the request, the same for all its attempts,
+    /// so that the server can tell the retries apart from new requests. The request is retried like an
+    /// idempotent one, see [IdempotencyKeys](crate::IdempotencyKeys).
+    pub fn with_idempotency_key(self) -> Self {
+        Self {
+            idempotency_key: Some(IdempotencyKey::RequestId),
+            ..self
+        }
+    }
+
+    /// Sends the given key as the `Idempotency-Key` header of the request, eg. one derived from the payment
+    /// it creates, to be reused when the caller sends the request again.
+    pub fn with_custom_idempotency_key(self, key: HeaderValue) -> Self {
+        Self {
+            idempotency_key: Some(IdempotencyKey::Custom(key)),
+            ..self
+        }
+    }
+
+    /// Sends the request, and streams the [ServerSentEvent](crate::ServerSentEvent)s of its `text/event-stream`
+    /// response as they are received.
+    ///
+    /// Whenever the connection drops, the request is sent again along with the `Last-Event-ID` header, after
+    /// the delay set by the server, 3 seconds by default. Every attempt gets fresh auth0 and tracing headers.
+    /// An error while reconnecting is yielded by the stream, which keeps on reconnecting if the server
+    /// couldn't be reached, up to 5 times in a row, and ends otherwise, eg. on a wrong status code. The stream
+    /// also ends when the server answers with `204 No Content`, or when the request body can't be cloned to be
+    /// sent again.
+    ///
+    /// The responses must be `text/event-stream` ones. A single event can't be larger than the maximum
+    /// response size of the request or of the bridge, 1 MiB by default; a larger one ends the stream with
+    /// [PrimaBridgeError::ResponseTooLarge](crate::prelude::PrimaBridgeError::ResponseTooLarge).
+    ///
+    /// Unless the request sets its own timeout, the connections are never timed out.
+    pub async fn send_sse(self) -> PrimaBridgeResult<EventStream>
+    where
+        Client: Send + Sync + 'static,
+    {
+        let mut request = self
+            .with_custom_header(ACCEPT, HeaderValue::from_static("text/event-stream"))
+            .with_custom_header(CACHE_CONTROL, HeaderValue::from_static("no-cache"))
+            .into_owned();
+        request.default_timeout = false;
+
+        let max_event_bytes = request.max_response_bytes.or(request.bridge.max_response_bytes);
+        let reconnect_request = request.try_clone();
+        let response = request.send_streaming().await?;
+        event_stream(reconnect_request, response, max_event_bytes)
+    }
+
+    /// Sends the request, and the ones of the following pages as described by the given [Pagination],
+    /// streaming the items of all the pages.
+    ///
+    /// Every page is requested with a clone of this request, so that it keeps its headers, retry policy and
+    /// so on. The stream ends after the first error.
+    pub fn paginate<T>(self, pagination: Pagination) -> PaginatedStream<T>
+    where
+        Client: Send + Sync + 'static,
+        T: DeserializeOwned + Send + 'static,
+    {
+        pagination::paginate(self.into_owned(), pagination)
+    }
+
+    /// Converts the request into one that owns all its data, including a clone of the bridge, so that it can
+    /// be stored or sent from a spawned task.
+    pub fn into_owned(self) -> RestRequest<'static, Client> {
+        RestRequest {
+            id: self.id,
+            bridge: Cow::Owned(self.bridge.into_owned()),
+            body: self.body,
+            method: self.method,
+            timeout: self.timeout,
+            default_timeout: self.default_timeout,
+            path: self.path.map(|path| Cow::Owned(path.into_owned())),
+            route: self.route.map(|route| Cow::Owned(route.into_owned())),
+            query_pairs: self
+                .query_pairs
+                .into_iter()
+                .map(|(name, value)| (Cow::Owned(name.into_owned()), Cow::Owned(value.into_owned())))
+                .collect(),
+            ignore_status_code: self.ignore_status_code,
+            custom_headers: self.custom_headers,
+            multipart: self.multipart,
+            retry_policy: self.retry_policy,
+            max_response_bytes: self.max_response_bytes,
+            idempotency_key: self.idempotency_key,
+        }
+    }
+
+    /// Clones the request, if its body and files are in memory. The clone gets a new id.
+    pub fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            id: Uuid::new_v4(),
+            bridge: self.bridge.clone(),
+            body: match &self.body {
+                Some(body) => Some(body.try_clone()?),
+                None => None,
+            },
+            method: self.method.clone(),
+            timeout: self.timeout,
+            default_timeout: self.default_timeout,
+            path: self.path.clone(),
+            route: self.route.clone(),
+            query_pairs: self.query_pairs.clone(),
+            ignore_status_code: self.ignore_status_code,
+            custom_headers: self.custom_headers.clone(),
+            multipart: match &self.multipart {
+                Some(multipart) => Some(multipart.try_clone()?),
+                None => None,
+            },
+            retry_policy: self.retry_policy.clone(),
+            max_response_bytes: self.max_response_bytes,
+            idempotency_key: self.idempotency_key.clone(),
+        })
+    }
+}
+
+impl<Client: BridgeClient> SealedRequest for RestRequest<'_, Client> {
+    fn request_timeout(&self) -> Option<Duration> {
+        match self.default_timeout {
+            true => Some(self.get_timeout()),
+            false => self.timeout,
+        }
+    }
 }
 
 #[async_trait]
@@ -81,9 +224,10 @@ impl<'a, Client: BridgeClient> DeliverableRequest<'a> for RestRequest<'a, Client
         Self { method, ..self }
     }
 
-    fn to(self, path: &'a str) -> Self {
+    fn to(self, path: impl Into<Cow<'a, str>>) -> Self {
         Self {
-            path: Some(path),
+            path: Some(path.into()),
+            route: None,
             ..self
         }
     }
@@ -96,34 +240,45 @@ impl<'a, Client: BridgeClient> DeliverableRequest<'a> for RestRequest<'a, Client
     }
 
     fn set_timeout(self, timeout: Duration) -> Self {
-        Self { timeout, ..self }
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
     }
 
     fn get_timeout(&self) -> Duration {
-        self.timeout
+        self.timeout.unwrap_or(self.bridge.default_timeout)
     }
 
     fn get_id(&self) -> Uuid {
         self.id
     }
 
-    fn get_bridge(&self) -> &'a BridgeImpl<Client> {
-        self.bridge
+    fn get_bridge(&self) -> &BridgeImpl<Client> {
+        &self.bridge
     }
 
     fn get_path(&self) -> Option<&str> {
-        self.path
+        self.path.as_deref()
+    }
+
+    fn get_route(&self) -> Option<&str> {
+        self.route.as_deref()
+    }
+
+    fn get_route_mut(&mut self) -> &mut Option<Cow<'a, str>> {
+        &mut self.route
     }
 
     fn endpoint(&self) -> Url {
         self.bridge.endpoint.clone()
     }
 
-    fn get_query_pairs(&self) -> &[(&'a str, &'a str)] {
+    fn get_query_pairs(&self) -> &[(Cow<'a, str>, Cow<'a, str>)] {
         self.query_pairs.as_slice()
     }
 
-    fn get_query_pairs_mut(&mut self) -> &mut Vec<(&'a str, &'a str)> {
+    fn get_query_pairs_mut(&mut self) -> &mut Vec<(Cow<'a, str>, Cow<'a, str>)> {
         &mut self.query_pairs
     }
 
@@ -143,6 +298,29 @@ impl<'a, Client: BridgeClient> DeliverableRequest<'a> for RestRequest<'a, Client
         &mut self.custom_headers
     }
 
+    fn get_retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    fn get_retry_policy_mut(&mut self) -> &mut Option<RetryPolicy> {
+        &mut self.retry_policy
+    }
+
+    fn get_max_response_bytes(&self) -> Option<u64> {
+        self.max_response_bytes
+    }
+
+    fn get_max_response_bytes_mut(&mut self) -> &mut Option<u64> {
+        &mut self.max_response_bytes
+    }
+
+    fn get_idempotency_key(&self) -> Option<HeaderValue> {
+        match &self.idempotency_key {
+            Some(idempotency_key) => Some(idempotency_key.value(self.id)),
+            None => idempotency::default_key(self),
+        }
+    }
+
     #[cfg(feature = "auth0")]
     fn get_auth0(&self) -> &Option<crate::auth0::RefreshingToken> {
         &self.bridge.auth0_opt
@@ -167,13 +345,14 @@ impl<'a, Client: BridgeClient> DeliverableRequest<'a> for RestRequest<'a, Client
 #[derive(Debug)]
 /// A [RestRequest] multipart form body.
 ///
-/// Only files can be included in the multipart body.  
-/// It can either be `Single` (one file) or `Multiple` (multiple files).
+/// It can either be `Single` (one file), `Multiple` (multiple files), or made of `Parts`, which can also
+/// contain text values and JSON documents next to the files, eg. the description or the checksum of an upload.
 ///
-/// Each file corresponds to a named field in the form data.
+/// Each file, value or document corresponds to a named field in the form data.
 pub enum RestMultipart {
     Single(MultipartFormFileField),
     Multiple(Vec<MultipartFormFileField>),
+    Parts(Vec<MultipartPart>),
 }
 impl RestMultipart {
     pub fn single<S>(form_field: S, file: MultipartFile) -> Self
@@ -188,21 +367,77 @@ impl RestMultipart {
         Self::Multiple(files)
     }
 
+    /// Creates an empty multipart form body, to be filled with the `with_` methods.
+    pub fn new() -> Self {
+        Self::Parts(Vec::new())
+    }
+
+    /// Adds a part to the form.
+    pub fn with_part(self, part: MultipartPart) -> Self {
+        let mut parts = self.into_parts();
+        parts.push(part);
+        Self::Parts(parts)
+    }
+
+    /// Adds a file field to the form.
+    pub fn with_file<S>(self, form_field: S, file: MultipartFile) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        self.with_part(MultipartPart::file(form_field, file))
+    }
+
+    /// Adds a plain text field to the form.
+    pub fn with_text<S>(self, form_field: S, value: impl Into<String>) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        self.with_part(MultipartPart::text(form_field, value))
+    }
+
+    /// Adds a field containing the given value serialized as JSON to the form.
+    pub fn with_json<S, T>(self, form_field: S, value: &T) -> PrimaBridgeResult<Self>
+    where
+        S: Into<Cow<'static, str>>,
+        T: Serialize + ?Sized,
+    {
+        Ok(self.with_part(MultipartPart::json(form_field, value)?))
+    }
+
+    fn into_parts(self) -> Vec<MultipartPart> {
+        match self {
+            Self::Single(field) => vec![field.into()],
+            Self::Multiple(fields) => fields.into_iter().map(Into::into).collect(),
+            Self::Parts(parts) => parts,
+        }
+    }
+
+    fn try_clone(&self) -> Option<Self> {
+        Some(match self {
+            Self::Single(field) => Self::Single(field.try_clone()?),
+            Self::Multiple(fields) => Self::Multiple(
+                fields
+                    .iter()
+                    .map(MultipartFormFileField::try_clone)
+                    .collect::<Option<_>>()?,
+            ),
+            Self::Parts(parts) => Self::Parts(parts.iter().map(MultipartPart::try_clone).collect::<Option<_>>()?),
+        })
+    }
+
     fn into_form(self) -> PrimaBridgeResult<Form> {
         let mut form = Form::new();
 
-        match self {
-            Self::Single(field) => {
-                form = form.part(field.field_name, field.file.into_part()?);
-            }
-
-            Self::Multiple(fields) => {
-                for field in fields {
-                    form = form.part(field.field_name, field.file.into_part()?);
-                }
-            }
+        for part in self.into_parts() {
+            form = form.part(part.field_name.clone(), part.into_part()?);
         }
 
         Ok(form)
     }
 }
+
+impl Default for RestMultipart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
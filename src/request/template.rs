@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::errors::{PrimaBridgeError, PrimaBridgeResult};
+
+/// The characters encoded in a path parameter: everything but the unreserved ones of RFC 3986, so that a
+/// parameter always stays a single path segment.
+const PATH_PARAMETER: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// Expands a path template like `users/{id}/orders/{order}`, replacing every placeholder with the
+/// percent-encoded value of the parameter with the same name.
+pub(crate) fn expand<K, V>(template: &str, params: impl IntoIterator<Item = (K, V)>) -> PrimaBridgeResult<String>
+where
+    K: AsRef<str>,
+    V: ToString,
+{
+    let invalid = |reason: String| PrimaBridgeError::InvalidPathTemplate {
+        template: template.to_string(),
+        reason,
+    };
+    let params: HashMap<String, String> = params
+        .into_iter()
+        .map(|(name, value)| (name.as_ref().to_string(), value.to_string()))
+        .collect();
+
+    let mut path = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(invalid("unmatched `}`".to_string()));
+        }
+        path.push_str(&rest[..start]);
+
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| invalid("unclosed `{`".to_string()))?;
+        let name = &rest[start + 1..end];
+        let value = params
+            .get(name)
+            .ok_or_else(|| invalid(format!("missing parameter `{name}`")))?;
+        // Empty and dot segments would change the structure of the path
+        if matches!(value.as_str(), "" | "." | "..") {
+            return Err(invalid(format!("parameter `{name}` can't be `{value}`")));
+        }
+        path.extend(utf8_percent_encode(value, PATH_PARAMETER));
+
+        rest = &rest[end + 1..];
+    }
+    path.push_str(rest);
+
+    Ok(path.trim_start_matches('/').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_every_parameter_as_a_single_segment() {
+        let path = expand(
+            "/users/{id}/orders/{order}",
+            [("id", "john/doe?admin=true"), ("order", "42 #1")],
+        )
+        .unwrap();
+
+        assert_eq!(path, "users/john%2Fdoe%3Fadmin%3Dtrue/orders/42%20%231");
+    }
+
+    #[test]
+    fn rejects_invalid_templates_and_parameters() {
+        let invalid = |template: &str, params: &[(&str, &str)]| {
+            matches!(
+                expand(template, params.iter().copied()),
+                Err(PrimaBridgeError::InvalidPathTemplate { .. })
+            )
+        };
+
+        assert!(invalid("users/{id", &[("id", "1")]));
+        assert!(invalid("users/id}", &[("id", "1")]));
+        assert!(invalid("users/{id}", &[("user", "1")]));
+        assert!(invalid("users/{id}/orders", &[("id", "..")]));
+        assert!(invalid("users/{id}/orders", &[("id", "")]));
+    }
+}
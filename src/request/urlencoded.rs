@@ -0,0 +1,672 @@
+use serde::ser::{
+    Error, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple, SerializeTupleStruct, Serializer,
+};
+use serde::Serialize;
+
+/// How arrays are encoded in query strings and form bodies. Nested objects are always encoded with brackets,
+/// eg. `user[name]=john`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrayEncoding {
+    /// `ids[]=1&ids[]=2`, as expected by Rails and PHP.
+    #[default]
+    Brackets,
+    /// `ids[0]=1&ids[1]=2`.
+    Indexed,
+    /// `ids=1&ids=2`.
+    Repeated,
+}
+
+type Pairs = Vec<(String, String)>;
+
+/// Flattens a serializable struct, map or sequence of pairs into the name/value pairs of a query string or form
+/// body, in the order of its fields.
+///
+/// `None` and unit values are skipped.
+pub(crate) fn to_pairs<T: Serialize + ?Sized>(value: &T, encoding: ArrayEncoding) -> Result<Pairs, serde_json::Error> {
+    let mut pairs = Vec::new();
+    value.serialize(PairsSerializer {
+        name: None,
+        encoding,
+        pairs: &mut pairs,
+    })?;
+    Ok(pairs)
+}
+
+fn unsupported(what: &str) -> serde_json::Error {
+    serde_json::Error::custom(format!("{what} can't be encoded in query strings and form bodies"))
+}
+
+macro_rules! serialize_scalars {
+    ($($method:ident: $type:ty),* $(,)?) => {
+        $(
+            fn $method(self, value: $type) -> Result<Self::Ok, Self::Error> {
+                self.scalar(value.to_string())
+            }
+        )*
+    };
+}
+
+/// Serializes a value into the pairs named after it, or after its fields when it's the top level value.
+struct PairsSerializer<'p> {
+    name: Option<String>,
+    encoding: ArrayEncoding,
+    pairs: &'p mut Pairs,
+}
+
+impl PairsSerializer<'_> {
+    fn scalar(self, value: String) -> Result<(), serde_json::Error> {
+        match self.name {
+            Some(name) => {
+                self.pairs.push((name, value));
+                Ok(())
+            }
+            None => Err(unsupported("values without a name")),
+        }
+    }
+
+    fn field(&mut self, key: String) -> PairsSerializer<'_> {
+        PairsSerializer {
+            name: Some(match &self.name {
+                Some(name) => format!("{name}[{key}]"),
+                None => key,
+            }),
+            encoding: self.encoding,
+            pairs: self.pairs,
+        }
+    }
+}
+
+impl<'p> Serializer for PairsSerializer<'p> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    type SerializeSeq = SeqSerializer<'p>;
+    type SerializeTuple = SeqSerializer<'p>;
+    type SerializeTupleStruct = SeqSerializer<'p>;
+    type SerializeTupleVariant = Impossible<(), serde_json::Error>;
+    type SerializeMap = MapSerializer<'p>;
+    type SerializeStruct = MapSerializer<'p>;
+    type SerializeStructVariant = Impossible<(), serde_json::Error>;
+
+    serialize_scalars!(
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+    );
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), Self::Error> {
+        Err(unsupported("bytes"))
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<(), Self::Error> {
+        self.scalar(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Self::Error> {
+        Err(unsupported("enum variants with data"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SeqSerializer { parent: self, index: 0 })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(unsupported("enum variants with data"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(MapSerializer {
+            parent: self,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(unsupported("enum variants with data"))
+    }
+}
+
+/// Serializes the elements of an array, or the name/value pairs of a top level sequence.
+struct SeqSerializer<'p> {
+    parent: PairsSerializer<'p>,
+    index: usize,
+}
+
+impl SerializeSeq for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let encoding = self.parent.encoding;
+        let index = self.index;
+        self.index += 1;
+        let Some(name) = &self.parent.name else {
+            return value.serialize(PairSerializer {
+                encoding,
+                pairs: self.parent.pairs,
+            });
+        };
+
+        let name = match encoding {
+            ArrayEncoding::Brackets => format!("{name}[]"),
+            ArrayEncoding::Indexed => format!("{name}[{index}]"),
+            ArrayEncoding::Repeated => name.clone(),
+        };
+        value.serialize(PairsSerializer {
+            name: Some(name),
+            encoding,
+            pairs: self.parent.pairs,
+        })
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl SerializeTuple for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl SerializeTupleStruct for SeqSerializer<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Serializes the fields of a struct or the entries of a map.
+struct MapSerializer<'p> {
+    parent: PairsSerializer<'p>,
+    key: Option<String>,
+}
+
+impl SerializeMap for MapSerializer<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self.key.take().unwrap_or_default();
+        value.serialize(self.parent.field(key))
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl SerializeStruct for MapSerializer<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self.parent.field(key.to_string()))
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Serializes an element of a top level sequence, which must be a name/value pair.
+struct PairSerializer<'p> {
+    encoding: ArrayEncoding,
+    pairs: &'p mut Pairs,
+}
+
+impl PairSerializer<'_> {
+    fn scalar(self, _: String) -> Result<(), serde_json::Error> {
+        Err(unsupported("values without a name"))
+    }
+}
+
+impl<'p> Serializer for PairSerializer<'p> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    type SerializeSeq = Impossible<(), serde_json::Error>;
+    type SerializeTuple = PairFieldsSerializer<'p>;
+    type SerializeTupleStruct = Impossible<(), serde_json::Error>;
+    type SerializeTupleVariant = Impossible<(), serde_json::Error>;
+    type SerializeMap = Impossible<(), serde_json::Error>;
+    type SerializeStruct = Impossible<(), serde_json::Error>;
+    type SerializeStructVariant = Impossible<(), serde_json::Error>;
+
+    serialize_scalars!(
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+    );
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), Self::Error> {
+        Err(unsupported("bytes"))
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> Result<(), Self::Error> {
+        Err(unsupported("values without a name"))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Self::Error> {
+        Err(unsupported("enum variants with data"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(unsupported("values without a name"))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        if len != 2 {
+            return Err(unsupported("tuples other than name/value pairs"));
+        }
+        Ok(PairFieldsSerializer {
+            encoding: self.encoding,
+            pairs: self.pairs,
+            name: None,
+        })
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(unsupported("values without a name"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(unsupported("enum variants with data"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(unsupported("values without a name"))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(unsupported("values without a name"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(unsupported("enum variants with data"))
+    }
+}
+
+/// Serializes the name, then the value, of a pair of a top level sequence.
+struct PairFieldsSerializer<'p> {
+    encoding: ArrayEncoding,
+    pairs: &'p mut Pairs,
+    name: Option<String>,
+}
+
+impl SerializeTuple for PairFieldsSerializer<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        match self.name.take() {
+            None => {
+                self.name = Some(value.serialize(KeySerializer)?);
+                Ok(())
+            }
+            Some(name) => value.serialize(PairsSerializer {
+                name: Some(name),
+                encoding: self.encoding,
+                pairs: self.pairs,
+            }),
+        }
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Serializes the key of a map, which must be a string or a scalar.
+struct KeySerializer;
+
+impl KeySerializer {
+    fn scalar(self, value: String) -> Result<String, serde_json::Error> {
+        Ok(value)
+    }
+}
+
+impl Serializer for KeySerializer {
+    type Ok = String;
+    type Error = serde_json::Error;
+    type SerializeSeq = Impossible<String, serde_json::Error>;
+    type SerializeTuple = Impossible<String, serde_json::Error>;
+    type SerializeTupleStruct = Impossible<String, serde_json::Error>;
+    type SerializeTupleVariant = Impossible<String, serde_json::Error>;
+    type SerializeMap = Impossible<String, serde_json::Error>;
+    type SerializeStruct = Impossible<String, serde_json::Error>;
+    type SerializeStructVariant = Impossible<String, serde_json::Error>;
+
+    serialize_scalars!(
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+    );
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<String, Self::Error> {
+        Err(unsupported("bytes"))
+    }
+
+    fn serialize_none(self) -> Result<String, Self::Error> {
+        Err(unsupported("missing names"))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, Self::Error> {
+        Err(unsupported("missing names"))
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<String, Self::Error> {
+        Err(unsupported("missing names"))
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<String, Self::Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<String, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, Self::Error> {
+        Err(unsupported("names other than strings and numbers"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(unsupported("names other than strings and numbers"))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(unsupported("names other than strings and numbers"))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(unsupported("names other than strings and numbers"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(unsupported("names other than strings and numbers"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(unsupported("names other than strings and numbers"))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(unsupported("names other than strings and numbers"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(unsupported("names other than strings and numbers"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn pairs(value: Value, encoding: ArrayEncoding) -> Vec<(String, String)> {
+        to_pairs(&value, encoding).unwrap()
+    }
+
+    fn expected(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn encodes_nested_objects_and_arrays() {
+        let value = json!({
+            "active": true,
+            "ids": [1, 2],
+            "page": { "cursor": null, "size": 10 },
+        });
+
+        assert_eq!(
+            pairs(value.clone(), ArrayEncoding::Brackets),
+            expected(&[("active", "true"), ("ids[]", "1"), ("ids[]", "2"), ("page[size]", "10")])
+        );
+        assert_eq!(
+            pairs(value.clone(), ArrayEncoding::Indexed),
+            expected(&[
+                ("active", "true"),
+                ("ids[0]", "1"),
+                ("ids[1]", "2"),
+                ("page[size]", "10")
+            ])
+        );
+        assert_eq!(
+            pairs(value, ArrayEncoding::Repeated),
+            expected(&[("active", "true"), ("ids", "1"), ("ids", "2"), ("page[size]", "10")])
+        );
+    }
+
+    #[test]
+    fn keeps_the_order_of_the_fields() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "snake_case")]
+        enum Sort {
+            Newest,
+        }
+
+        #[derive(Serialize)]
+        struct Search {
+            query: &'static str,
+            sort: Sort,
+            cursor: Option<u32>,
+            filters: Filters,
+        }
+
+        #[derive(Serialize)]
+        struct Filters {
+            model: &'static str,
+            brand: &'static str,
+        }
+
+        let search = Search {
+            query: "car",
+            sort: Sort::Newest,
+            cursor: None,
+            filters: Filters {
+                model: "500",
+                brand: "fiat",
+            },
+        };
+
+        assert_eq!(
+            to_pairs(&search, ArrayEncoding::Brackets).unwrap(),
+            expected(&[
+                ("query", "car"),
+                ("sort", "newest"),
+                ("filters[model]", "500"),
+                ("filters[brand]", "fiat")
+            ])
+        );
+    }
+
+    #[test]
+    fn encodes_sequences_of_pairs() {
+        let pairs = [("b", vec![2, 3]), ("a", vec![1])];
+
+        assert_eq!(
+            to_pairs(&pairs, ArrayEncoding::Repeated).unwrap(),
+            expected(&[("b", "2"), ("b", "3"), ("a", "1")])
+        );
+    }
+
+    #[test]
+    fn rejects_values_without_names() {
+        assert!(to_pairs(&[1, 2], ArrayEncoding::Brackets).is_err());
+        assert!(to_pairs("hello", ArrayEncoding::Brackets).is_err());
+    }
+}
@@ -1,15 +1,27 @@
 use std::fmt::Debug;
 
+use bytes::Bytes;
+use futures::stream;
 use reqwest::{header::HeaderMap, StatusCode, Url};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::codec::{Codec, Decode};
 use crate::errors::BodyStructure;
 use crate::prelude::*;
 use crate::response::graphql::{ParsedGraphqlResponse, ParsedGraphqlResponseExt};
 
 pub mod graphql;
+mod json_stream;
+mod sse;
+mod streaming;
+
+pub use json_stream::JsonStream;
+pub(crate) use sse::event_stream;
+pub use sse::{EventStream, ServerSentEvent};
+pub use streaming::{BodyStream, StreamingResponse};
 
 #[derive(Debug, PartialEq)]
 enum RequestType {
@@ -71,6 +83,21 @@ impl Response {
         self.request_type == RequestType::GraphQL
     }
 
+    /// Returns a copy of the response, for the requests coalesced with the one which received it.
+    pub(crate) fn duplicate(&self) -> Self {
+        Self {
+            url: self.url.clone(),
+            response_body: self.response_body.clone(),
+            status_code: self.status_code,
+            response_headers: self.response_headers.clone(),
+            request_id: self.request_id,
+            request_type: match self.request_type {
+                RequestType::Rest => RequestType::Rest,
+                RequestType::GraphQL => RequestType::GraphQL,
+            },
+        }
+    }
+
     /// Returns an `HeaderMap` of response headers.
     pub fn headers(&self) -> &HeaderMap {
         &self.response_headers
@@ -94,6 +121,34 @@ impl Response {
         extract_inner_json(self.url, selectors, json_value)
     }
 
+    /// Deserializes every non-blank line of a newline delimited JSON (`application/x-ndjson`) body, one at a
+    /// time.
+    pub fn json_lines<T>(self) -> JsonStream<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        json_stream::json_lines(self.url, Box::pin(stream::iter([Ok(Bytes::from(self.response_body))])))
+    }
+
+    /// Deserializes the elements of the array found by following the given keys, like
+    /// [get_data](Self::get_data) does, one at a time rather than the whole array at once.
+    pub fn json_array_items<T>(self, selectors: &[&str]) -> JsonStream<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let mut selectors = selectors.to_vec();
+        if self.is_graphql() {
+            selectors.insert(0, "data");
+        };
+        let body = Box::pin(stream::iter([Ok(Bytes::from(self.response_body))]));
+        json_stream::json_array_items(self.url, body, &selectors)
+    }
+
+    /// Decodes the whole body with the given [Codec], eg. `decode::<MessagePack, Person>()`.
+    pub fn decode<C: Codec, T: Decode<C>>(&self) -> PrimaBridgeResult<T> {
+        T::decode(&self.response_body)
+    }
+
     /// This functions return a Result with a [ParsedGraphqlResponse]
     /// Look at the type documentation for more specifications
     pub fn parse_graphql_response<T>(&self) -> PrimaBridgeResult<ParsedGraphqlResponse<T>>
@@ -121,9 +176,9 @@ impl Response {
     }
 }
 
-fn extract_inner_json<T>(url: Url, selectors: Vec<&str>, json_value: Value) -> PrimaBridgeResult<T>
+pub(crate) fn extract_inner_json<T>(url: Url, selectors: Vec<&str>, json_value: Value) -> PrimaBridgeResult<T>
 where
-    for<'de> T: Deserialize<'de> + Debug,
+    for<'de> T: Deserialize<'de>,
 {
     let inner_result = selectors
         .into_iter()
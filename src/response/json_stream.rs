@@ -0,0 +1,350 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+
+use crate::codec::{Decode, Json};
+use crate::prelude::*;
+use crate::response::BodyStream;
+
+/// The items deserialized one at a time from a JSON lines body, or from the elements of a JSON array.
+pub type JsonStream<T> = Pin<Box<dyn Stream<Item = PrimaBridgeResult<T>> + Send>>;
+
+/// Splits a body, fed in chunks of any size, into the raw JSON of its items.
+trait Framing: Send + 'static {
+    fn feed(&mut self, chunk: &[u8], items: &mut VecDeque<Vec<u8>>);
+
+    /// Called once the whole body has been fed, returns why it's incomplete, if it is.
+    fn finish(&mut self, items: &mut VecDeque<Vec<u8>>) -> Result<(), String>;
+}
+
+/// Newline delimited JSON, where every non-blank line is an item.
+#[derive(Debug, Default)]
+struct Lines {
+    line: Vec<u8>,
+}
+
+impl Lines {
+    fn push_line(line: &[u8], items: &mut VecDeque<Vec<u8>>) {
+        if !line.trim_ascii().is_empty() {
+            items.push_back(line.trim_ascii().to_vec());
+        }
+    }
+}
+
+impl Framing for Lines {
+    fn feed(&mut self, mut chunk: &[u8], items: &mut VecDeque<Vec<u8>>) {
+        while let Some(end) = chunk.iter().position(|byte| *byte == b'\n') {
+            if self.line.is_empty() {
+                Self::push_line(&chunk[..end], items);
+            } else {
+                self.line.extend_from_slice(&chunk[..end]);
+                Self::push_line(&self.line, items);
+                self.line.clear();
+            }
+            chunk = &chunk[end + 1..];
+        }
+        self.line.extend_from_slice(chunk);
+    }
+
+    fn finish(&mut self, items: &mut VecDeque<Vec<u8>>) -> Result<(), String> {
+        Self::push_line(&std::mem::take(&mut self.line), items);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+enum Frame {
+    Object { key: Option<String>, expecting_key: bool },
+    Array,
+}
+
+/// The elements of the array found by following the given keys from the top level object, or of the top
+/// level array when there are none.
+///
+/// Only the keys of the objects enclosing the array are parsed while scanning the body, the elements are
+/// left to be deserialized one at a time.
+#[derive(Debug)]
+struct ArrayElements {
+    selectors: Vec<String>,
+    buffer: Vec<u8>,
+    stack: Vec<Frame>,
+    in_string: bool,
+    escaped: bool,
+    string_start: usize,
+    /// The depth of the elements, once the array has been found.
+    elements_depth: Option<usize>,
+    element_start: Option<usize>,
+    done: bool,
+}
+
+impl ArrayElements {
+    fn new(selectors: &[&str]) -> Self {
+        Self {
+            selectors: selectors.iter().map(|selector| selector.to_string()).collect(),
+            buffer: vec![],
+            stack: vec![],
+            in_string: false,
+            escaped: false,
+            string_start: 0,
+            elements_depth: None,
+            element_start: None,
+            done: false,
+        }
+    }
+
+    fn is_at_selected_path(&self) -> bool {
+        self.stack.len() == self.selectors.len()
+            && self
+                .stack
+                .iter()
+                .zip(&self.selectors)
+                .all(|(frame, selector)| matches!(frame, Frame::Object { key: Some(key), .. } if key == selector))
+    }
+
+    fn is_between_elements(&self) -> bool {
+        self.elements_depth == Some(self.stack.len())
+    }
+
+    fn push_element(&mut self, end: usize, items: &mut VecDeque<Vec<u8>>) {
+        if let Some(start) = self.element_start.take() {
+            items.push_back(self.buffer[start..end].trim_ascii_end().to_vec());
+        }
+    }
+
+    fn scan(&mut self, from: usize, items: &mut VecDeque<Vec<u8>>) {
+        for index in from..self.buffer.len() {
+            let byte = self.buffer[index];
+
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                    if self.elements_depth.is_none() {
+                        let key = serde_json::from_slice(&self.buffer[self.string_start..=index]).ok();
+                        if let Some(Frame::Object {
+                            key: current_key,
+                            expecting_key: expecting_key @ true,
+                        }) = self.stack.last_mut()
+                        {
+                            *current_key = key;
+                            *expecting_key = false;
+                        }
+                    }
+                }
+                continue;
+            }
+
+            if self.is_between_elements()
+                && self.element_start.is_none()
+                && !matches!(byte, b',' | b']')
+                && !byte.is_ascii_whitespace()
+            {
+                self.element_start = Some(index);
+            }
+
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    self.string_start = index;
+                }
+                b'{' => self.stack.push(Frame::Object {
+                    key: None,
+                    expecting_key: true,
+                }),
+                b'[' => {
+                    if self.elements_depth.is_none() && self.is_at_selected_path() {
+                        self.elements_depth = Some(self.stack.len() + 1);
+                    }
+                    self.stack.push(Frame::Array);
+                }
+                b',' => {
+                    if self.is_between_elements() {
+                        self.push_element(index, items);
+                    } else if let Some(Frame::Object { key, expecting_key }) = self.stack.last_mut() {
+                        *key = None;
+                        *expecting_key = true;
+                    }
+                }
+                b']' if self.is_between_elements() => {
+                    self.push_element(index, items);
+                    self.done = true;
+                    return;
+                }
+                b'}' | b']' => {
+                    self.stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Drops the bytes that have been scanned and are not needed anymore.
+    fn compact(&mut self) {
+        let keep_from = match self.element_start {
+            Some(start) => start,
+            None if self.in_string && self.elements_depth.is_none() => self.string_start,
+            None => self.buffer.len(),
+        };
+        self.buffer.drain(..keep_from);
+        self.string_start = self.string_start.saturating_sub(keep_from);
+        self.element_start = self.element_start.map(|start| start - keep_from);
+    }
+}
+
+impl Framing for ArrayElements {
+    fn feed(&mut self, chunk: &[u8], items: &mut VecDeque<Vec<u8>>) {
+        if self.done {
+            return;
+        }
+        let from = self.buffer.len();
+        self.buffer.extend_from_slice(chunk);
+        self.scan(from, items);
+        self.compact();
+    }
+
+    fn finish(&mut self, _items: &mut VecDeque<Vec<u8>>) -> Result<(), String> {
+        match (self.done, self.elements_depth) {
+            (true, _) => Ok(()),
+            (false, Some(_)) => Err("the body ended before the end of the array".to_string()),
+            (false, None) if self.selectors.is_empty() => Err("the body is not a JSON array".to_string()),
+            (false, None) => Err(format!("no JSON array found at `{}`", self.selectors.join("."))),
+        }
+    }
+}
+
+struct Items<F> {
+    url: Url,
+    body: BodyStream,
+    framing: F,
+    items: VecDeque<Vec<u8>>,
+    ended: bool,
+}
+
+fn json_stream<T, F>(url: Url, body: BodyStream, framing: F) -> JsonStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+    F: Framing,
+{
+    let items = Items {
+        url,
+        body,
+        framing,
+        items: VecDeque::new(),
+        ended: false,
+    };
+
+    Box::pin(futures::stream::unfold(items, |mut state| async move {
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                return Some((<T as Decode<Json>>::decode(&item), state));
+            }
+            if state.ended {
+                return None;
+            }
+
+            match state.body.next().await {
+                Some(Ok(chunk)) => state.framing.feed(&chunk, &mut state.items),
+                Some(Err(error)) => {
+                    state.ended = true;
+                    return Some((Err(error), state));
+                }
+                None => {
+                    state.ended = true;
+                    if let Err(reason) = state.framing.finish(&mut state.items) {
+                        let error = PrimaBridgeError::JsonStreamError {
+                            url: state.url.clone(),
+                            reason,
+                        };
+                        state.items.clear();
+                        return Some((Err(error), state));
+                    }
+                }
+            }
+        }
+    }))
+}
+
+/// Deserializes every non-blank line of a newline delimited JSON body.
+pub(crate) fn json_lines<T>(url: Url, body: BodyStream) -> JsonStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    json_stream(url, body, Lines::default())
+}
+
+/// Deserializes the elements of the array found by following the given keys.
+pub(crate) fn json_array_items<T>(url: Url, body: BodyStream, selectors: &[&str]) -> JsonStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    json_stream(url, body, ArrayElements::new(selectors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split<F: Framing>(mut framing: F, chunks: &[&str]) -> (Vec<String>, Result<(), String>) {
+        let mut items = VecDeque::new();
+        for chunk in chunks {
+            framing.feed(chunk.as_bytes(), &mut items);
+        }
+        let result = framing.finish(&mut items);
+        let items = items.into_iter().map(|item| String::from_utf8(item).unwrap()).collect();
+        (items, result)
+    }
+
+    #[test]
+    fn splits_lines_across_chunks() {
+        let (items, result) = split(Lines::default(), &["{\"a\":", "1}\r\n\n  \n{\"a\"", ":2}\n{\"a\":3}"]);
+
+        assert_eq!(items, vec!["{\"a\":1}", "{\"a\":2}", "{\"a\":3}"]);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn splits_the_elements_of_the_selected_array_one_byte_at_a_time() {
+        let body = r#"{"meta": {"items": ["not", "these"]}, "data": {"total": 3, "items": [
+            {"id": 1, "tags": ["a]", "b,"]}, 2 , "three\"]" , null]}, "next": [4]}"#;
+        let chunks: Vec<String> = body.chars().map(String::from).collect();
+        let chunks: Vec<&str> = chunks.iter().map(String::as_str).collect();
+
+        let (items, result) = split(ArrayElements::new(&["data", "items"]), &chunks);
+
+        assert_eq!(
+            items,
+            vec![r#"{"id": 1, "tags": ["a]", "b,"]}"#, "2", r#""three\"]""#, "null"]
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn splits_the_elements_of_the_top_level_array() {
+        let (items, result) = split(ArrayElements::new(&[]), &["[ ]"]);
+        assert!(items.is_empty());
+        assert!(result.is_ok());
+
+        let (items, result) = split(ArrayElements::new(&[]), &[" [1, [2,", " 3]]"]);
+        assert_eq!(items, vec!["1", "[2, 3]"]);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn reports_a_missing_or_truncated_array() {
+        let (_, result) = split(ArrayElements::new(&["items"]), &[r#"{"data": {"items": [1]}}"#]);
+        assert_eq!(result.unwrap_err(), "no JSON array found at `items`");
+
+        let (_, result) = split(ArrayElements::new(&[]), &[r#"{"items": [1]}"#]);
+        assert_eq!(result.unwrap_err(), "the body is not a JSON array");
+
+        let (items, result) = split(ArrayElements::new(&["items"]), &[r#"{"items": [1, 2"#]);
+        assert_eq!(items, vec!["1"]);
+        assert_eq!(result.unwrap_err(), "the body ended before the end of the array");
+    }
+}
@@ -0,0 +1,384 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+
+use crate::codec::{Decode, Json};
+use crate::prelude::*;
+use crate::response::BodyStream;
+use crate::BridgeClient;
+
+/// The events received by [RestRequest::send_sse](crate::RestRequest::send_sse).
+pub type EventStream = Pin<Box<dyn Stream<Item = PrimaBridgeResult<ServerSentEvent>> + Send>>;
+
+/// The delay before reconnecting, until the server sets its own with the `retry` field.
+const DEFAULT_RETRY: Duration = Duration::from_secs(3);
+
+/// The size an event can grow to, unless the request or the bridge set a maximum response size.
+const DEFAULT_MAX_EVENT_BYTES: u64 = 1024 * 1024;
+
+/// How many times in a row the stream tries to reconnect to a server which can't be reached.
+const MAX_RECONNECTION_ATTEMPTS: u32 = 5;
+
+const EVENT_STREAM: &str = "text/event-stream";
+
+/// An event received from a `text/event-stream` response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerSentEvent {
+    id: Option<String>,
+    event: String,
+    data: String,
+    retry: Option<Duration>,
+}
+
+impl ServerSentEvent {
+    /// Returns the last event id set by the server, sent back in the `Last-Event-ID` header when reconnecting.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns the type of the event, `message` unless set by the server.
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    /// Returns the data of the event, with the lines of multi-line data joined by `\n`.
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Returns the reconnection delay set by the server along with this event, if any.
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    /// Deserializes the data of the event as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> PrimaBridgeResult<T> {
+        <T as Decode<Json>>::decode(self.data.as_bytes())
+    }
+}
+
+/// Parses a `text/event-stream` body, as specified by the HTML standard, fed in chunks of any size.
+#[derive(Debug)]
+struct EventParser {
+    /// How large the line being received, along with the data of the event, can get.
+    max_event_bytes: u64,
+    line: Vec<u8>,
+    /// Whether the last chunk ended with `\r`, so that a `\n` starting the next one doesn't end another line.
+    skip_line_feed: bool,
+    last_event_id: Option<String>,
+    event: Option<String>,
+    data: Option<String>,
+    retry: Option<Duration>,
+    /// The last reconnection delay set by the server, by any block, even one without data.
+    reconnection_time: Option<Duration>,
+}
+
+impl Default for EventParser {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_EVENT_BYTES)
+    }
+}
+
+impl EventParser {
+    fn new(max_event_bytes: u64) -> Self {
+        Self {
+            max_event_bytes,
+            line: Vec::new(),
+            skip_line_feed: false,
+            last_event_id: None,
+            event: None,
+            data: None,
+            retry: None,
+            reconnection_time: None,
+        }
+    }
+
+    /// Parses the events of the chunk. Returns `false`, dropping the rest of the chunk, as soon as the event
+    /// being received gets larger than the maximum size.
+    fn feed(&mut self, chunk: &[u8], events: &mut VecDeque<ServerSentEvent>) -> bool {
+        for &byte in chunk {
+            let skip_line_feed = std::mem::take(&mut self.skip_line_feed);
+            match byte {
+                b'\n' if skip_line_feed => {}
+                b'\n' | b'\r' => {
+                    self.skip_line_feed = byte == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                        events.push_back(event);
+                    }
+                }
+                _ if self.event_bytes() >= self.max_event_bytes => return false,
+                _ => self.line.push(byte),
+            }
+        }
+        true
+    }
+
+    fn event_bytes(&self) -> u64 {
+        (self.line.len() + self.data.as_ref().map_or(0, String::len)) as u64
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<ServerSentEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => match &mut self.data {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => self.data = Some(value.to_string()),
+            },
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(millis) = value.parse() {
+                    self.retry = Some(Duration::from_millis(millis));
+                    self.reconnection_time = self.retry;
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Dispatches the event buffered so far, if it has any data.
+    fn dispatch(&mut self) -> Option<ServerSentEvent> {
+        let event = self.event.take();
+        let retry = self.retry.take();
+        Some(ServerSentEvent {
+            id: self.last_event_id.clone(),
+            event: event.unwrap_or_else(|| "message".to_string()),
+            data: self.data.take()?,
+            retry,
+        })
+    }
+}
+
+/// The state of an [EventStream] between two events.
+struct Connection<Client: BridgeClient + 'static> {
+    /// The request sent again to reconnect, missing if its body can't be cloned.
+    request: Option<RestRequest<'static, Client>>,
+    body: Option<BodyStream>,
+    parser: EventParser,
+    events: VecDeque<ServerSentEvent>,
+    closed: bool,
+    /// The error yielded after the events received before it, ending the stream.
+    error: Option<PrimaBridgeError>,
+    url: Url,
+    failed_reconnections: u32,
+}
+
+/// Streams the events of an established `text/event-stream` response, reconnecting with a clone of `request`
+/// whenever the connection drops.
+pub(crate) fn event_stream<Client>(
+    request: Option<RestRequest<'static, Client>>,
+    response: StreamingResponse,
+    max_event_bytes: Option<u64>,
+) -> PrimaBridgeResult<EventStream>
+where
+    Client: BridgeClient + Send + Sync + 'static,
+{
+    check_content_type(&response)?;
+    let connection = Connection {
+        request,
+        url: response.url().clone(),
+        body: Some(response.into_stream()),
+        parser: EventParser::new(max_event_bytes.unwrap_or(DEFAULT_MAX_EVENT_BYTES)),
+        events: VecDeque::new(),
+        closed: false,
+        error: None,
+        failed_reconnections: 0,
+    };
+
+    Ok(Box::pin(futures::stream::unfold(
+        connection,
+        |mut connection| async move {
+            loop {
+                if let Some(event) = connection.events.pop_front() {
+                    return Some((Ok(event), connection));
+                }
+                if connection.closed {
+                    let error = connection.error.take()?;
+                    return Some((Err(error), connection));
+                }
+
+                if let Some(body) = &mut connection.body {
+                    match body.next().await {
+                        Some(Ok(chunk)) => {
+                            if !connection.parser.feed(&chunk, &mut connection.events) {
+                                connection.closed = true;
+                                connection.error = Some(PrimaBridgeError::ResponseTooLarge {
+                                    url: connection.url.clone(),
+                                    limit: connection.parser.max_event_bytes,
+                                });
+                            }
+                        }
+                        // The connection dropped, hence the incomplete event gets discarded
+                        Some(Err(_)) | None => {
+                            connection.body = None;
+                            connection.parser = EventParser {
+                                last_event_id: connection.parser.last_event_id.take(),
+                                reconnection_time: connection.parser.reconnection_time,
+                                ..EventParser::new(connection.parser.max_event_bytes)
+                            };
+                        }
+                    }
+                    continue;
+                }
+
+                // Without a request whose body can be cloned, the stream can't reconnect
+                let mut request = connection.request.as_ref().and_then(RestRequest::try_clone)?;
+                tokio::time::sleep(connection.parser.reconnection_time.unwrap_or(DEFAULT_RETRY)).await;
+                if let Some(last_event_id) = &connection.parser.last_event_id {
+                    if let Ok(value) = HeaderValue::from_str(last_event_id) {
+                        request = request.with_custom_header(HeaderName::from_static("last-event-id"), value);
+                    }
+                }
+
+                // Sending the request again refreshes the auth0 token and the tracing headers
+                match request.send_streaming().await {
+                    // The server asks not to reconnect anymore
+                    Ok(response) if response.status_code() == &reqwest::StatusCode::NO_CONTENT => return None,
+                    Ok(response) => match check_content_type(&response) {
+                        Ok(()) => {
+                            connection.failed_reconnections = 0;
+                            connection.body = Some(response.into_stream());
+                        }
+                        Err(error) => {
+                            connection.closed = true;
+                            return Some((Err(error), connection));
+                        }
+                    },
+                    // The server can't be reached at the moment, so the stream keeps on reconnecting, for a while
+                    Err(error @ PrimaBridgeError::HttpError { .. }) => {
+                        connection.failed_reconnections += 1;
+                        connection.closed = connection.failed_reconnections >= MAX_RECONNECTION_ATTEMPTS;
+                        return Some((Err(error), connection));
+                    }
+                    Err(error) => {
+                        connection.closed = true;
+                        return Some((Err(error), connection));
+                    }
+                }
+            }
+        },
+    )))
+}
+
+/// Fails unless the response is a `text/event-stream` one.
+fn check_content_type(response: &StreamingResponse) -> PrimaBridgeResult<()> {
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    let mime_type = content_type.and_then(|content_type| content_type.split(';').next());
+    match mime_type {
+        Some(mime_type) if mime_type.trim().eq_ignore_ascii_case(EVENT_STREAM) => Ok(()),
+        _ => Err(PrimaBridgeError::UnexpectedContentType {
+            url: response.url().clone(),
+            content_type: content_type.map(str::to_string),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(chunks: &[&str]) -> Vec<ServerSentEvent> {
+        let mut parser = EventParser::default();
+        let mut events = VecDeque::new();
+        for chunk in chunks {
+            parser.feed(chunk.as_bytes(), &mut events);
+        }
+        events.into()
+    }
+
+    fn event(id: Option<&str>, event: &str, data: &str, retry: Option<u64>) -> ServerSentEvent {
+        ServerSentEvent {
+            id: id.map(str::to_string),
+            event: event.to_string(),
+            data: data.to_string(),
+            retry: retry.map(Duration::from_millis),
+        }
+    }
+
+    #[test]
+    fn parses_the_fields_of_the_events() {
+        let events = parse(&[
+            ": a comment\n",
+            "event: progress\nid: 1\nretry: 500\ndata: {\"done\": 10}\n\n",
+            "data:first line\ndata: second line\n\n",
+        ]);
+
+        assert_eq!(
+            events,
+            vec![
+                event(Some("1"), "progress", "{\"done\": 10}", Some(500)),
+                event(Some("1"), "message", "first line\nsecond line", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_every_line_ending_across_chunks() {
+        let events = parse(&["data: a\r", "\n\r", "\ndata: b\r\rdata", ": c\n", "\n"]);
+
+        assert_eq!(
+            events,
+            vec![
+                event(None, "message", "a", None),
+                event(None, "message", "b", None),
+                event(None, "message", "c", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_events_without_data() {
+        let events = parse(&["event: ping\n\nid: 7\n\ndata\n\n"]);
+
+        assert_eq!(events, vec![event(Some("7"), "message", "", None)]);
+    }
+
+    #[test]
+    fn keeps_the_reconnection_time_of_a_block_without_data() {
+        let mut parser = EventParser::default();
+        let mut events = VecDeque::new();
+
+        parser.feed(
+            b"retry: 250
+
+data: a
+
+",
+            &mut events,
+        );
+
+        assert_eq!(Vec::from(events), vec![event(None, "message", "a", None)]);
+        assert_eq!(parser.reconnection_time, Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn stops_at_an_event_larger_than_the_maximum_size() {
+        let mut parser = EventParser::new(8);
+        let mut events = VecDeque::new();
+
+        assert!(parser.feed(b"data: a\n\n", &mut events));
+        assert!(!parser.feed(b"data: 1234\ndata: 5678\n\n", &mut events));
+        assert_eq!(Vec::from(events), vec![event(None, "message", "a", None)]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::{self, Debug};
+use std::path::Path;
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use reqwest::{header::HeaderMap, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::OwnedSemaphorePermit;
+use uuid::Uuid;
+
+use crate::prelude::*;
+use crate::response::{json_stream, JsonStream};
+
+/// The body of a [StreamingResponse], as a stream of chunks.
+pub type BodyStream = Pin<Box<dyn Stream<Item = PrimaBridgeResult<Bytes>> + Send>>;
+
+/// A server response whose body hasn't been read yet, returned by
+/// [send_streaming](crate::DeliverableRequest::send_streaming).
+///
+/// The body is received while it's consumed, so that large downloads don't need to fit in memory.
+pub struct StreamingResponse {
+    url: Url,
+    status_code: StatusCode,
+    response_headers: HeaderMap,
+    request_id: Uuid,
+    body: BodyStream,
+}
+
+impl StreamingResponse {
+    pub(crate) fn new(
+        url: Url,
+        response: reqwest::Response,
+        request_id: Uuid,
+        max_response_bytes: Option<u64>,
+        concurrency_permit: Option<OwnedSemaphorePermit>,
+    ) -> Self {
+        let status_code = response.status();
+        let response_headers = response.headers().clone();
+        let body_url = url.clone();
+        let mut received: u64 = 0;
+        let body = response.bytes_stream().map(move |chunk| {
+            // Keeps the concurrency slot taken until the body has been read, or dropped
+            let _ = &concurrency_permit;
+            let chunk = chunk.map_err(|source| PrimaBridgeError::HttpError {
+                url: body_url.clone(),
+                source,
+            })?;
+            received += chunk.len() as u64;
+            match max_response_bytes {
+                Some(limit) if received > limit => Err(PrimaBridgeError::ResponseTooLarge {
+                    url: body_url.clone(),
+                    limit,
+                }),
+                _ => Ok(chunk),
+            }
+        });
+
+        Self {
+            url,
+            status_code,
+            response_headers,
+            request_id,
+            body: Box::pin(body),
+        }
+    }
+
+    /// Returns an `HeaderMap` of response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.response_headers
+    }
+
+    pub fn status_code(&self) -> &StatusCode {
+        &self.status_code
+    }
+
+    /// returns `true` if the response is successful
+    pub fn is_ok(&self) -> bool {
+        self.status_code.is_success()
+    }
+
+    pub fn request_id(&self) -> Uuid {
+        self.request_id
+    }
+
+    pub(crate) fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the body as a stream of chunks.
+    pub fn into_stream(self) -> BodyStream {
+        self.body
+    }
+
+    /// Deserializes every non-blank line of a newline delimited JSON (`application/x-ndjson`) body, as soon
+    /// as it's received.
+    pub fn json_lines<T>(self) -> JsonStream<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        json_stream::json_lines(self.url, self.body)
+    }
+
+    /// Deserializes the elements of the array found by following the given keys from the top level object, or
+    /// of the top level array when there are none, as soon as each one is received.
+    pub fn json_array_items<T>(self, selectors: &[&str]) -> JsonStream<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        json_stream::json_array_items(self.url, self.body, selectors)
+    }
+
+    /// Writes the whole body to the given writer, returning the number of bytes written.
+    pub async fn write_to<W>(self, writer: &mut W) -> PrimaBridgeResult<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let url = self.url;
+        let write_error = |source| PrimaBridgeError::ResponseBodyWriteError {
+            url: url.clone(),
+            source,
+        };
+
+        let mut body = self.body;
+        let mut written = 0;
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await.map_err(write_error)?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await.map_err(write_error)?;
+
+        Ok(written)
+    }
+
+    /// Writes the whole body to the file at the given path, which gets created or truncated, returning the
+    /// number of bytes written.
+    pub async fn write_to_file(self, path: impl AsRef<Path>) -> PrimaBridgeResult<u64> {
+        let mut file =
+            tokio::fs::File::create(path)
+                .await
+                .map_err(|source| PrimaBridgeError::ResponseBodyWriteError {
+                    url: self.url.clone(),
+                    source,
+                })?;
+        self.write_to(&mut file).await
+    }
+}
+
+impl Debug for StreamingResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamingResponse")
+            .field("url", &self.url)
+            .field("status_code", &self.status_code)
+            .field("response_headers", &self.response_headers)
+            .field("request_id", &self.request_id)
+            .finish_non_exhaustive()
+    }
+}
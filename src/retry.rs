@@ -0,0 +1,260 @@
+use std::time::Duration;
+
+use rand::RngExt;
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+
+use crate::errors::PrimaBridgeError;
+
+/// A kind of transport error that can be considered retryable by a [RetryPolicy].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryableError {
+    /// The connection to the server could not be established.
+    Connect,
+    /// The request, or the connection, timed out.
+    Timeout,
+    /// The request failed while being sent.
+    Request,
+    /// The request or response body failed while being streamed.
+    Body,
+}
+
+impl RetryableError {
+    fn matches(&self, error: &reqwest::Error) -> bool {
+        match self {
+            Self::Connect => error.is_connect(),
+            Self::Timeout => error.is_timeout(),
+            Self::Request => error.is_request(),
+            Self::Body => error.is_body(),
+        }
+    }
+}
+
+/// Determines if and how a failed request gets retried.
+///
+/// A policy can be set for all the requests made by a bridge via
+/// [BridgeBuilder::with_retry_policy](crate::builder::BridgeBuilderInner::with_retry_policy),
+/// and overridden on a single request via
+/// [DeliverableRequest::with_retry_policy](crate::DeliverableRequest::with_retry_policy).
+///
+/// Between two attempts the request waits for an exponentially growing delay, capped at `max_backoff`.
+/// When jitter is enabled (the default) the actual delay is a random value between zero and the computed one.
+///
+/// Requests whose body is a stream (eg. a file) or a multipart form cannot be replayed, so they are
+/// always sent exactly once.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    backoff_multiplier: f64,
+    jitter: bool,
+    retryable_status_codes: Vec<StatusCode>,
+    retryable_errors: Vec<RetryableError>,
+    idempotent_only: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            backoff_multiplier: 2.0,
+            jitter: true,
+            retryable_status_codes: vec![
+                StatusCode::REQUEST_TIMEOUT,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retryable_errors: vec![RetryableError::Connect, RetryableError::Timeout],
+            idempotent_only: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that never retries.
+    ///
+    /// Useful to opt a single request out of the retry policy configured on the bridge.
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the maximum number of attempts, including the first one. Defaults to 3.
+    pub fn with_max_attempts(self, max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..self
+        }
+    }
+
+    /// Sets the delay before the first retry and the upper bound of every delay.
+    ///
+    /// Defaults to 100 milliseconds and 10 seconds.
+    pub fn with_backoff(self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            initial_backoff,
+            max_backoff,
+            ..self
+        }
+    }
+
+    /// Sets the factor the delay gets multiplied by after every attempt. Defaults to 2.
+    pub fn with_backoff_multiplier(self, backoff_multiplier: f64) -> Self {
+        Self {
+            backoff_multiplier,
+            ..self
+        }
+    }
+
+    /// Enables or disables the random jitter applied to the delays. Enabled by default.
+    pub fn with_jitter(self, jitter: bool) -> Self {
+        Self { jitter, ..self }
+    }
+
+    /// Sets the response status codes that trigger a retry.
+    ///
+    /// Defaults to 408, 429, 502, 503 and 504.
+    pub fn with_retryable_status_codes(self, retryable_status_codes: Vec<StatusCode>) -> Self {
+        Self {
+            retryable_status_codes,
+            ..self
+        }
+    }
+
+    /// Sets the kinds of transport errors that trigger a retry.
+    ///
+    /// Defaults to [RetryableError::Connect] and [RetryableError::Timeout].
+    pub fn with_retryable_errors(self, retryable_errors: Vec<RetryableError>) -> Self {
+        Self {
+            retryable_errors,
+            ..self
+        }
+    }
+
+    /// Whether only requests with an idempotent method (GET, HEAD, OPTIONS, TRACE, PUT and DELETE) get retried.
+    /// The requests with an `Idempotency-Key` header are retried whatever their method, see
+    /// [IdempotencyKeys](crate::IdempotencyKeys).
+    ///
+    /// Defaults to `true`.
+    pub fn with_idempotent_only(self, idempotent_only: bool) -> Self {
+        Self {
+            idempotent_only,
+            ..self
+        }
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Returns how many attempts a request with the given method, and maybe an idempotency key, is allowed to make.
+    pub(crate) fn attempts_for(&self, method: &Method, has_idempotency_key: bool) -> u32 {
+        if self.idempotent_only && !is_idempotent(method) && !has_idempotency_key {
+            1
+        } else {
+            self.max_attempts
+        }
+    }
+
+    pub(crate) fn is_retryable_status(&self, status_code: StatusCode) -> bool {
+        self.retryable_status_codes.contains(&status_code)
+    }
+
+    pub(crate) fn is_retryable_error(&self, error: &PrimaBridgeError) -> bool {
+        match error {
+            PrimaBridgeError::WrongStatusCode(_, status_code) => self.is_retryable_status(*status_code),
+            PrimaBridgeError::HttpError { source, .. } => self.retryable_errors.iter().any(|kind| kind.matches(source)),
+            _ => false,
+        }
+    }
+
+    /// Returns the delay to wait for after the given (1-based) failed attempt.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1) as i32;
+        let factor = self.backoff_multiplier.max(1.0).powi(exponent).min(f64::MAX);
+        // A delay too long to be represented is capped like any other
+        let delay = Duration::try_from_secs_f64(self.initial_backoff.as_secs_f64() * factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            delay.mul_f64(rand::rng().random_range(0.0..=1.0))
+        } else {
+            delay
+        }
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE | Method::PUT | Method::DELETE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max() {
+        let policy = RetryPolicy::default()
+            .with_jitter(false)
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(500));
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+        assert_eq!(policy.backoff(100), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn jittered_backoff_never_exceeds_computed_delay() {
+        let policy = RetryPolicy::default().with_backoff(Duration::from_millis(100), Duration::from_secs(1));
+        let without_jitter = policy.clone().with_jitter(false);
+
+        for attempt in 1..10 {
+            assert!(policy.backoff(attempt) <= without_jitter.backoff(attempt));
+        }
+    }
+
+    #[test]
+    fn caps_the_backoffs_too_long_to_be_represented() {
+        let policy = RetryPolicy::default()
+            .with_jitter(false)
+            .with_backoff(Duration::from_secs(1 << 60), Duration::MAX)
+            .with_backoff_multiplier(1e300);
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(1 << 60));
+        assert_eq!(policy.backoff(3), Duration::MAX);
+    }
+
+    #[test]
+    fn non_idempotent_methods_are_sent_once_by_default() {
+        let policy = RetryPolicy::default().with_max_attempts(5);
+
+        assert_eq!(policy.attempts_for(&Method::GET, false), 5);
+        assert_eq!(policy.attempts_for(&Method::PUT, false), 5);
+        assert_eq!(policy.attempts_for(&Method::POST, false), 1);
+        assert_eq!(policy.attempts_for(&Method::PATCH, false), 1);
+        assert_eq!(policy.attempts_for(&Method::POST, true), 5);
+        assert_eq!(policy.with_idempotent_only(false).attempts_for(&Method::POST, false), 5);
+    }
+}
@@ -0,0 +1,84 @@
+use reqwest::{Certificate, ClientBuilder, Identity};
+use serde::Deserialize;
+
+use crate::errors::BridgeBuildError;
+
+/// A version of the TLS protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum TlsVersion {
+    #[serde(rename = "1.0")]
+    Tls1_0,
+    #[serde(rename = "1.1")]
+    Tls1_1,
+    #[serde(rename = "1.2")]
+    Tls1_2,
+    #[serde(rename = "1.3")]
+    Tls1_3,
+}
+
+impl From<TlsVersion> for reqwest::tls::Version {
+    fn from(version: TlsVersion) -> Self {
+        match version {
+            TlsVersion::Tls1_0 => reqwest::tls::Version::TLS_1_0,
+            TlsVersion::Tls1_1 => reqwest::tls::Version::TLS_1_1,
+            TlsVersion::Tls1_2 => reqwest::tls::Version::TLS_1_2,
+            TlsVersion::Tls1_3 => reqwest::tls::Version::TLS_1_3,
+        }
+    }
+}
+
+/// A client certificate, with its private key, presented to the servers requiring mutual TLS.
+#[derive(Clone)]
+pub enum TlsIdentity {
+    /// A PEM encoded certificate chain and its private key (PKCS#8, PKCS#1 or SEC1), in a single buffer.
+    Pem(Vec<u8>),
+    /// A DER encoded PKCS#12 archive and its password.
+    ///
+    /// The bridge will use the TLS implementation of the platform instead of rustls.
+    #[cfg_attr(docsrs, doc(cfg(feature = "native-tls")))]
+    #[cfg(feature = "native-tls")]
+    Pkcs12 { der: Vec<u8>, password: String },
+}
+
+impl TlsIdentity {
+    /// Creates an identity from a PEM encoded certificate chain and private key.
+    pub fn pem(pem: impl Into<Vec<u8>>) -> Self {
+        Self::Pem(pem.into())
+    }
+
+    /// Creates an identity from a DER encoded PKCS#12 archive and its password.
+    #[cfg_attr(docsrs, doc(cfg(feature = "native-tls")))]
+    #[cfg(feature = "native-tls")]
+    pub fn pkcs12(der: impl Into<Vec<u8>>, password: impl Into<String>) -> Self {
+        Self::Pkcs12 {
+            der: der.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Parses the identity, returning how to configure a client builder to present it.
+    pub(crate) fn configure(self) -> Result<Box<dyn FnOnce(ClientBuilder) -> ClientBuilder>, BridgeBuildError> {
+        match self {
+            Self::Pem(pem) => {
+                let identity = Identity::from_pem(&pem).map_err(|error| BridgeBuildError::Tls(error.into()))?;
+                // PEM identities are only understood by rustls, which isn't the default once native-tls is enabled
+                Ok(Box::new(move |builder| builder.tls_backend_rustls().identity(identity)))
+            }
+            #[cfg(feature = "native-tls")]
+            Self::Pkcs12 { der, password } => {
+                let identity =
+                    Identity::from_pkcs12_der(&der, &password).map_err(|error| BridgeBuildError::Tls(error.into()))?;
+                Ok(Box::new(move |builder| builder.tls_backend_native().identity(identity)))
+            }
+        }
+    }
+}
+
+/// Parses a PEM bundle holding at least one certificate.
+pub(crate) fn parse_certificates(pem: &[u8]) -> Result<Vec<Certificate>, BridgeBuildError> {
+    let certificates = Certificate::from_pem_bundle(pem).map_err(|error| BridgeBuildError::Tls(error.into()))?;
+    if certificates.is_empty() {
+        return Err(BridgeBuildError::Tls("no certificate found in the PEM bundle".into()));
+    }
+    Ok(certificates)
+}
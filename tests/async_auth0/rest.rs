@@ -266,3 +266,70 @@ async fn decompresses_gzip_responses() -> Result<(), Box<dyn Error>> {
     assert_eq!(result, "world!");
     Ok(())
 }
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn try_build_reports_auth0_errors() -> Result<(), Box<dyn Error>> {
+    // No token mock is registered, so fetching the token fails
+    let server = Server::new_async().await;
+    let config = prima_bridge::auth0::Config {
+        token_url: format!("{}/token", server.url()).parse()?,
+        caller: "caller".to_string(),
+        audience: "audience".to_string(),
+        cache_type: prima_bridge::auth0::CacheType::Inmemory,
+        token_encryption_key: "32char_long_token_encryption_key".to_string(),
+        check_interval: Duration::from_secs(10),
+        staleness_check_percentage: StalenessCheckPercentage::default(),
+        client_id: "client_id".to_string(),
+        client_secret: "client_secret".to_string(),
+        jwks_url: format!("{}/jwks", server.url()).parse()?,
+        scope: None,
+    };
+
+    let result = Bridge::builder()
+        .with_auth0(config)
+        .await
+        .try_build(server.url().parse()?);
+
+    assert!(matches!(result, Err(BridgeBuildError::Auth0(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn builds_a_bridge_with_auth0_from_config() -> Result<(), Box<dyn Error>> {
+    let mut server = Server::new_async().await;
+    let _mocks = crate::async_auth0::Auth0Mocks::new(&mut server).await;
+    let mock = server
+        .mock("GET", "/")
+        .match_header(
+            reqwest::header::AUTHORIZATION.as_str(),
+            mockito::Matcher::Regex("^Bearer .+".to_string()),
+        )
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .create_async()
+        .await;
+
+    let config: prima_bridge::BridgeConfig = serde_json::from_value(json!({
+        "endpoint": server.url(),
+        "auth0": {
+            "token_url": format!("{}/token", server.url()),
+            "jwks_url": format!("{}/jwks", server.url()),
+            "caller": "caller",
+            "audience": "audience",
+            "cache_type": { "type": "inmemory" },
+            "check_interval": "10s",
+            "client_id": "client_id",
+            "client_secret": "client_secret",
+        },
+    }))?;
+    let bridge = config.build().await?;
+
+    let result: String = RestRequest::new(&bridge).send().await?.get_data(&["hello"])?;
+
+    assert_eq!("world!", result.as_str());
+    mock.assert_async().await;
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use reqwest::StatusCode;
+
+use prima_bridge::{prelude::*, BatchOrder};
+
+#[tokio::test]
+async fn sends_all_the_requests_with_bounded_concurrency() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+    let mut mocks = vec![];
+    for quote in 0..9 {
+        let in_flight = in_flight.clone();
+        let max_in_flight = max_in_flight.clone();
+        let mock = server
+            .mock("GET", format!("/quotes/{quote}").as_str())
+            .with_chunked_body(move |writer| {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(50));
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                writer.write_all(format!("{{\"quote\": {quote}}}").as_bytes())
+            })
+            .expect(1)
+            .create_async()
+            .await;
+        mocks.push(mock);
+    }
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let requests = (0..9).map(|quote| Request::get(&bridge).to(format!("quotes/{quote}")));
+    let results = Bridge::send_all(requests, 3).await;
+
+    let quotes = results
+        .into_iter()
+        .map(|result| result?.get_data(&["quote"]))
+        .collect::<PrimaBridgeResult<Vec<u32>>>()?;
+    assert_eq!(quotes, (0..9).collect::<Vec<_>>());
+    assert!(max_in_flight.load(Ordering::SeqCst) <= 3);
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_the_failures_along_with_the_successes() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    server.mock("GET", "/quotes/1").with_body("{}").create_async().await;
+    server.mock("GET", "/quotes/2").with_status(404).create_async().await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let requests = [1, 2].map(|quote| Request::get(&bridge).to(format!("quotes/{quote}")));
+    let results = Bridge::send_all(requests, 10).await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        results[1],
+        Err(PrimaBridgeError::WrongStatusCode(_, StatusCode::NOT_FOUND))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn streams_the_results_in_the_requested_order() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/slow")
+        .with_chunked_body(|writer| {
+            std::thread::sleep(Duration::from_millis(200));
+            writer.write_all(b"\"slow\"")
+        })
+        .create_async()
+        .await;
+    server.mock("GET", "/fast").with_body("\"fast\"").create_async().await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+    let send = |order| {
+        let requests = ["slow", "fast"].map(|path| Request::get(&bridge).to(path));
+        Bridge::send_all_streaming(requests, 2, order)
+            .map(|result| result?.get_data::<String>(&[]))
+            .collect::<Vec<_>>()
+    };
+
+    let completed = send(BatchOrder::Completion)
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    let ordered = send(BatchOrder::Input)
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(completed, vec!["fast", "slow"]);
+    assert_eq!(ordered, vec!["slow", "fast"]);
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use std::error::Error;
+
+use http::Extensions;
+use reqwest_middleware::{Middleware, Next};
+
+use prima_bridge::prelude::*;
+use prima_bridge::{CircuitBreakerPolicy, CircuitState};
+
+struct PassThrough;
+
+#[async_trait::async_trait]
+impl Middleware for PassThrough {
+    async fn handle(
+        &self,
+        req: reqwest::Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        next.run(req, extensions).await
+    }
+}
+
+#[tokio::test]
+async fn try_build_creates_a_working_bridge() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/")
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder().try_build(server.url().parse()?)?;
+    let result: String = RestRequest::new(&bridge).send().await?.get_data(&["hello"])?;
+
+    assert_eq!("world!", result.as_str());
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[test]
+fn try_build_reports_client_errors() -> Result<(), Box<dyn Error>> {
+    let result = Bridge::builder()
+        .with_user_agent("invalid\nuser agent")
+        .try_build("http://prima.it".parse()?);
+
+    assert!(matches!(result, Err(BridgeBuildError::HttpClient(_))));
+
+    Ok(())
+}
+
+#[test]
+fn try_build_with_middleware_reports_client_errors() -> Result<(), Box<dyn Error>> {
+    let result = Bridge::builder()
+        .try_with_middleware(PassThrough)?
+        .with_user_agent("invalid\nuser agent")
+        .try_build("http://prima.it".parse()?);
+
+    assert!(matches!(result, Err(BridgeBuildError::HttpClient(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn try_build_with_middleware_creates_a_working_bridge() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(200).create_async().await;
+
+    let bridge = Bridge::builder()
+        .try_with_middleware(PassThrough)?
+        .try_build(server.url().parse()?)?;
+    RestRequest::new(&bridge).send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn builds_a_bridge_with_an_existing_client() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/")
+        .match_header("user-agent", "shared-client")
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = reqwest::Client::builder().user_agent("shared-client").build()?;
+    let bridge = Bridge::builder()
+        .with_client(client.clone())
+        .try_build(server.url().parse()?)?;
+    RestRequest::new(&bridge).send().await?;
+
+    let client = reqwest_middleware::ClientBuilder::new(client).with(PassThrough).build();
+    let bridge = Bridge::builder().with_client(client).try_build(server.url().parse()?)?;
+    RestRequest::new(&bridge).send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rejects_the_client_options_set_before_an_existing_client() -> Result<(), Box<dyn Error>> {
+    let result = Bridge::builder()
+        .with_user_agent("ignored")
+        .with_client(reqwest::Client::new())
+        .try_build("http://prima.it".parse()?);
+
+    assert!(matches!(
+        result,
+        Err(BridgeBuildError::InvalidConfig { field, .. }) if field == "client"
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn with_endpoint_keeps_the_defaults_of_the_bridge() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mut other_server = mockito::Server::new_async().await;
+    let mock = other_server
+        .mock("GET", "/")
+        .match_header("x-tenant", "prima")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_default_headers(vec![(
+            reqwest::header::HeaderName::from_static("x-tenant"),
+            reqwest::header::HeaderValue::from_static("prima"),
+        )])
+        .with_circuit_breaker(CircuitBreakerPolicy::default().with_minimum_requests(1))
+        .try_build(server.url().parse()?)?;
+    let other_bridge = bridge.with_endpoint(other_server.url().parse()?);
+
+    // Opening the breaker of the first bridge doesn't affect the other one
+    server.mock("GET", "/").with_status(500).create_async().await;
+    let _ = RestRequest::new(&bridge).send().await;
+    assert_eq!(bridge.circuit_state(), Some(CircuitState::Open));
+    assert_eq!(other_bridge.circuit_state(), Some(CircuitState::Closed));
+
+    RestRequest::new(&other_bridge).send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
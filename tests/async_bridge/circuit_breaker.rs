@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::time::Duration;
+
+use prima_bridge::{prelude::*, CircuitBreakerPolicy, CircuitState};
+
+fn circuit_breaker_policy() -> CircuitBreakerPolicy {
+    CircuitBreakerPolicy::default()
+        .with_minimum_requests(2)
+        .with_failure_rate_threshold(0.5)
+        .with_half_open_requests(1)
+}
+
+#[tokio::test]
+async fn bridge_without_circuit_breaker_has_no_state() -> Result<(), Box<dyn Error>> {
+    let bridge = Bridge::builder().build("http://prima.it".parse()?);
+
+    assert_eq!(bridge.circuit_state(), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn open_circuit_short_circuits_requests() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(500).expect(2).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_circuit_breaker(circuit_breaker_policy().with_cool_down(Duration::from_secs(60)))
+        .build(server.url().parse()?);
+
+    for _ in 0..2 {
+        let result = RestRequest::new(&bridge).send().await;
+        assert!(matches!(result, Err(PrimaBridgeError::WrongStatusCode(..))));
+    }
+    assert_eq!(bridge.circuit_state(), Some(CircuitState::Open));
+
+    let result = RestRequest::new(&bridge).send().await;
+    assert!(matches!(result, Err(PrimaBridgeError::CircuitOpen(_))));
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn circuit_is_shared_across_clones() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server.mock("GET", "/").with_status(503).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_circuit_breaker(circuit_breaker_policy().with_cool_down(Duration::from_secs(60)))
+        .build(server.url().parse()?);
+    let cloned_bridge = bridge.clone();
+
+    for _ in 0..2 {
+        let _ = RestRequest::new(&bridge).send().await;
+    }
+
+    assert_eq!(cloned_bridge.circuit_state(), Some(CircuitState::Open));
+    let result = RestRequest::new(&cloned_bridge).send().await;
+    assert!(matches!(result, Err(PrimaBridgeError::CircuitOpen(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn client_errors_do_not_open_the_circuit() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server.mock("GET", "/").with_status(404).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_circuit_breaker(circuit_breaker_policy())
+        .build(server.url().parse()?);
+
+    for _ in 0..5 {
+        let result = RestRequest::new(&bridge).send().await;
+        assert!(matches!(result, Err(PrimaBridgeError::WrongStatusCode(..))));
+    }
+    assert_eq!(bridge.circuit_state(), Some(CircuitState::Closed));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn successful_trial_request_closes_the_circuit() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _failing = server.mock("GET", "/").with_status(500).expect(2).create_async().await;
+    let _succeeding = server
+        .mock("GET", "/")
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_circuit_breaker(circuit_breaker_policy().with_cool_down(Duration::from_millis(50)))
+        .build(server.url().parse()?);
+
+    for _ in 0..2 {
+        let _ = RestRequest::new(&bridge).send().await;
+    }
+    assert_eq!(bridge.circuit_state(), Some(CircuitState::Open));
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert_eq!(bridge.circuit_state(), Some(CircuitState::HalfOpen));
+
+    let result: String = RestRequest::new(&bridge).send().await?.get_data(&["hello"])?;
+    assert_eq!("world!", result.as_str());
+    assert_eq!(bridge.circuit_state(), Some(CircuitState::Closed));
+
+    Ok(())
+}
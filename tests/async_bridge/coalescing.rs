@@ -0,0 +1,143 @@
+use std::error::Error;
+use std::time::Duration;
+
+use futures::future::join_all;
+use reqwest::header::{HeaderName, HeaderValue, ACCEPT_LANGUAGE, AUTHORIZATION};
+use reqwest::StatusCode;
+
+use prima_bridge::prelude::*;
+use prima_bridge::RequestCoalescing;
+
+/// Answers slowly enough for the concurrent requests to be in flight at the same time.
+fn slow_body(body: &'static str) -> impl Fn(&mut dyn std::io::Write) -> std::io::Result<()> + Send + Sync + 'static {
+    move |writer| {
+        std::thread::sleep(Duration::from_millis(200));
+        writer.write_all(body.as_bytes())
+    }
+}
+
+#[tokio::test]
+async fn coalesces_the_identical_requests_in_flight() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/vehicles")
+        .with_chunked_body(slow_body("[\"car\"]"))
+        .expect(1)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder()
+        .with_request_coalescing(RequestCoalescing::new())
+        .build(server.url().parse()?);
+
+    let responses = join_all((0..10).map(|_| RestRequest::new(&bridge).to("vehicles").send())).await;
+
+    for response in responses {
+        assert_eq!(response?.get_data::<Vec<String>>(&[])?, vec!["car".to_string()]);
+    }
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_coalesce_the_requests_with_different_selected_headers() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mut mocks = vec![];
+    for language in ["it", "en"] {
+        let mock = server
+            .mock("GET", "/vehicles")
+            .match_header("accept-language", language)
+            .with_chunked_body(slow_body("[]"))
+            .expect(1)
+            .create_async()
+            .await;
+        mocks.push(mock);
+    }
+    let bridge = Bridge::builder()
+        .with_request_coalescing(RequestCoalescing::new().with_header(ACCEPT_LANGUAGE))
+        .build(server.url().parse()?);
+    let request = |language: &'static str| {
+        RestRequest::new(&bridge)
+            .to("vehicles")
+            .with_custom_header(ACCEPT_LANGUAGE, HeaderValue::from_static(language))
+            // Not a selected header
+            .with_custom_header(HeaderName::from_static("x-caller"), HeaderValue::from_static(language))
+            .send()
+    };
+
+    let responses = join_all([request("it"), request("en"), request("it"), request("en")]).await;
+
+    for response in responses {
+        response?;
+    }
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_coalesce_the_requests_with_different_credentials() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mut mocks = vec![];
+    for user in ["alice", "bob"] {
+        let mock = server
+            .mock("GET", "/profile")
+            .match_header("authorization", format!("Bearer {user}").as_str())
+            .with_chunked_body(slow_body(user))
+            .expect(1)
+            .create_async()
+            .await;
+        mocks.push(mock);
+    }
+    let bridge = Bridge::builder()
+        .with_request_coalescing(RequestCoalescing::new())
+        .build(server.url().parse()?);
+    let request = |user: &'static str| {
+        RestRequest::new(&bridge)
+            .to("profile")
+            .with_custom_header(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {user}")).unwrap())
+            .send()
+    };
+
+    let responses = join_all([request("alice"), request("bob")]).await;
+
+    let bodies = responses
+        .into_iter()
+        .map(|response| Ok(String::from_utf8(response?.raw_body().clone())?))
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    assert_eq!(bodies, vec!["alice", "bob"]);
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn shares_the_wrong_status_code() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/vehicles")
+        .with_status(404)
+        .with_chunked_body(slow_body(""))
+        .expect(1)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder()
+        .with_request_coalescing(RequestCoalescing::new())
+        .build(server.url().parse()?);
+
+    let responses = join_all((0..3).map(|_| RestRequest::new(&bridge).to("vehicles").send())).await;
+
+    for response in responses {
+        assert!(matches!(
+            response,
+            Err(PrimaBridgeError::WrongStatusCode(_, StatusCode::NOT_FOUND))
+        ));
+    }
+    mock.assert_async().await;
+
+    Ok(())
+}
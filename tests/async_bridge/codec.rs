@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fmt::Debug;
+
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+
+use prima_bridge::codec::{Codec, Decode, Encode, Json};
+use prima_bridge::prelude::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+fn person() -> Person {
+    Person {
+        name: "John".to_string(),
+        age: 42,
+    }
+}
+
+/// Sends the value encoded with the codec `C` to a server echoing it back, and decodes the response.
+async fn round_trip<C, T>(value: &T, encoded: Vec<u8>) -> Result<T, Box<dyn Error>>
+where
+    C: Codec,
+    T: Encode<C> + Decode<C>,
+{
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/people")
+        .match_header(CONTENT_TYPE, C::CONTENT_TYPE)
+        .match_header(ACCEPT, C::CONTENT_TYPE)
+        .match_body(encoded.clone())
+        .with_status(200)
+        .with_header(CONTENT_TYPE, C::CONTENT_TYPE)
+        .with_body(encoded)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let response = Request::post(&bridge)
+        .to("people")
+        .encoded_body::<C>(value)?
+        .accepting::<C>()
+        .send()
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(response.decode::<C, T>()?)
+}
+
+async fn assert_round_trip<C, T>(value: T, encoded: Vec<u8>) -> Result<(), Box<dyn Error>>
+where
+    C: Codec,
+    T: Encode<C> + Decode<C> + PartialEq + Debug,
+{
+    assert_eq!(round_trip::<C, T>(&value, encoded).await?, value);
+    Ok(())
+}
+
+#[tokio::test]
+async fn round_trips_json_bodies() -> Result<(), Box<dyn Error>> {
+    assert_round_trip::<Json, _>(person(), serde_json::to_vec(&person())?).await
+}
+
+#[cfg(feature = "msgpack")]
+#[tokio::test]
+async fn round_trips_msgpack_bodies() -> Result<(), Box<dyn Error>> {
+    use prima_bridge::codec::MessagePack;
+
+    assert_round_trip::<MessagePack, _>(person(), rmp_serde::to_vec_named(&person())?).await
+}
+
+#[cfg(feature = "cbor")]
+#[tokio::test]
+async fn round_trips_cbor_bodies() -> Result<(), Box<dyn Error>> {
+    use prima_bridge::codec::Cbor;
+
+    let mut encoded = Vec::new();
+    ciborium::into_writer(&person(), &mut encoded)?;
+    assert_round_trip::<Cbor, _>(person(), encoded).await
+}
+
+#[cfg(feature = "xml")]
+#[tokio::test]
+async fn round_trips_xml_bodies() -> Result<(), Box<dyn Error>> {
+    use prima_bridge::codec::Xml;
+
+    let encoded = b"<Person><name>John</name><age>42</age></Person>".to_vec();
+    assert_round_trip::<Xml, _>(person(), encoded).await
+}
+
+#[cfg(feature = "protobuf")]
+#[tokio::test]
+async fn round_trips_protobuf_bodies() -> Result<(), Box<dyn Error>> {
+    use prima_bridge::codec::Protobuf;
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Person {
+        #[prost(string, tag = "1")]
+        name: String,
+        #[prost(uint32, tag = "2")]
+        age: u32,
+    }
+
+    let person = Person {
+        name: "John".to_string(),
+        age: 42,
+    };
+    // The name, as field 1 of type string, then the age, as field 2 of type varint
+    let encoded = b"\x0a\x04John\x10\x2a".to_vec();
+    assert_round_trip::<Protobuf, _>(person, encoded).await
+}
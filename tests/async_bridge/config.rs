@@ -0,0 +1,108 @@
+use std::error::Error;
+
+use serde_json::json;
+
+use prima_bridge::prelude::*;
+use prima_bridge::BridgeConfig;
+
+#[tokio::test]
+async fn builds_a_bridge_from_config() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/")
+        .match_header("user-agent", "prima-test")
+        .match_header("x-tenant", "prima")
+        .match_query(mockito::Matcher::UrlEncoded("locale".to_string(), "it".to_string()))
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .create_async()
+        .await;
+
+    let config: BridgeConfig = serde_json::from_value(json!({
+        "endpoint": server.url(),
+        "user_agent": "prima-test",
+        "default_timeout": "5s",
+        "default_headers": { "x-tenant": "prima" },
+        "default_query_pairs": { "locale": "it" },
+        "retry": { "max_attempts": 2, "initial_backoff": "1ms", "max_backoff": "5ms" },
+        "concurrency_limit": { "max_concurrency": 4 },
+    }))?;
+    let bridge = config.build().await?;
+
+    let result: String = RestRequest::new(&bridge).send().await?.get_data(&["hello"])?;
+
+    assert_eq!("world!", result.as_str());
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reports_invalid_fields() -> Result<(), Box<dyn Error>> {
+    let config: BridgeConfig = serde_json::from_value(json!({
+        "endpoint": "http://prima.it",
+        "default_headers": { "x-tenant": "prima\n" },
+    }))?;
+
+    match config.build().await {
+        Err(BridgeBuildError::InvalidConfig { field, .. }) => assert_eq!(field, "default_headers.x-tenant"),
+        Err(other) => panic!("expected InvalidConfig, got: {other:?}"),
+        Ok(_) => panic!("expected InvalidConfig"),
+    }
+
+    let config: BridgeConfig = serde_json::from_value(json!({ "endpoint": "not a url" }))?;
+
+    assert!(matches!(
+        config.build().await,
+        Err(BridgeBuildError::InvalidConfig { field, .. }) if field == "endpoint"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn rejects_unknown_fields() {
+    let result = serde_json::from_value::<BridgeConfig>(json!({
+        "endpoint": "http://prima.it",
+        "user_agnet": "prima-test",
+    }));
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn builds_a_bridge_with_connection_options_from_config() -> Result<(), Box<dyn Error>> {
+    let mut proxy = mockito::Server::new_async().await;
+    let mock = proxy
+        .mock("GET", "/")
+        .match_header("host", "prima.invalid")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let config: BridgeConfig = serde_json::from_value(json!({
+        "endpoint": "http://prima.invalid",
+        "proxy": { "url": proxy.url(), "scope": "http", "no_proxy": ["localhost"] },
+        "connect_timeout": "1s",
+        "tcp_keepalive": "30s",
+        "http1_only": true,
+        "local_address": "127.0.0.1",
+    }))?;
+    let bridge = config.build().await?;
+    RestRequest::new(&bridge).send().await?;
+
+    mock.assert_async().await;
+
+    let config: BridgeConfig = serde_json::from_value(json!({
+        "endpoint": "http://prima.it",
+        "http1_only": true,
+        "http2_prior_knowledge": true,
+    }))?;
+
+    assert!(matches!(
+        config.build().await,
+        Err(BridgeBuildError::InvalidConfig { field, .. }) if field == "http2_prior_knowledge"
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+
+use http::Extensions;
+use mockito::Matcher;
+use reqwest_middleware::{Middleware, Next};
+
+use prima_bridge::prelude::*;
+use prima_bridge::Proxy;
+
+struct PassThrough;
+
+#[async_trait::async_trait]
+impl Middleware for PassThrough {
+    async fn handle(
+        &self,
+        req: reqwest::Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        next.run(req, extensions).await
+    }
+}
+
+#[tokio::test]
+async fn sends_the_requests_through_the_proxy() -> Result<(), Box<dyn Error>> {
+    let mut proxy = mockito::Server::new_async().await;
+    let mock = proxy
+        .mock("GET", "/hello")
+        .match_header("host", "prima.invalid")
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_proxy(Proxy::http(proxy.url().parse()?))
+        .try_build("http://prima.invalid".parse()?)?;
+    let result: String = RestRequest::new(&bridge)
+        .to("hello")
+        .send()
+        .await?
+        .get_data(&["hello"])?;
+
+    assert_eq!("world!", result.as_str());
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn bypasses_the_proxy_for_the_no_proxy_hosts() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(200).create_async().await;
+
+    // Nothing listens on the proxy address, so the request only succeeds if it's sent directly
+    let bridge = Bridge::builder()
+        .with_proxy(Proxy::all("http://127.0.0.1:9".parse()?).with_no_proxy(["127.0.0.1"]))
+        .try_build(server.url().parse()?)?;
+    RestRequest::new(&bridge).send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn configures_the_client_after_adding_middlewares() -> Result<(), Box<dyn Error>> {
+    let mut proxy = mockito::Server::new_async().await;
+    let mock = proxy
+        .mock("GET", "/")
+        .match_header("host", "prima.invalid")
+        .match_header("user-agent", "bridge.rs")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .try_with_middleware(PassThrough)?
+        .with_user_agent("bridge.rs")
+        .with_proxy(Proxy::all(proxy.url().parse()?))
+        .with_connect_timeout(Duration::from_secs(1))
+        .with_tcp_keepalive(Some(Duration::from_secs(30)))
+        .try_build("http://prima.invalid".parse()?)?;
+    RestRequest::new(&bridge).send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ignores_the_proxies_when_disabled() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(200).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_proxy(Proxy::all("http://127.0.0.1:9".parse()?))
+        .without_proxies()
+        .try_build(server.url().parse()?)?;
+    RestRequest::new(&bridge).send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn speaks_the_configured_http_version() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    // HTTP/2 requests carry the host in the `:authority` pseudo header instead
+    let http1_mock = server
+        .mock("GET", "/http1")
+        .match_header("host", Matcher::Any)
+        .with_status(200)
+        .create_async()
+        .await;
+    let http2_mock = server
+        .mock("GET", "/http2")
+        .match_header("host", Matcher::Missing)
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder().with_http1_only().try_build(server.url().parse()?)?;
+    RestRequest::new(&bridge).to("http1").send().await?;
+
+    let bridge = Bridge::builder()
+        .with_http2_prior_knowledge()
+        .try_build(server.url().parse()?)?;
+    RestRequest::new(&bridge).to("http2").send().await?;
+
+    http1_mock.assert_async().await;
+    http2_mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn binds_the_local_address() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(200).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_local_address(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .try_build(server.url().parse()?)?;
+    RestRequest::new(&bridge).send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
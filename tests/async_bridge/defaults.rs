@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::time::Duration;
+
+use mockito::Matcher;
+use reqwest::header::{HeaderName, HeaderValue};
+
+use prima_bridge::prelude::*;
+
+#[tokio::test]
+async fn default_headers_are_sent_with_every_request() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/")
+        .match_header("x-tenant", "prima")
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_default_headers(vec![(
+            HeaderName::from_static("x-tenant"),
+            HeaderValue::from_static("prima"),
+        )])
+        .build(server.url().parse()?);
+
+    RestRequest::new(&bridge).send().await?;
+    Request::get(&bridge).send().await?;
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn request_headers_take_precedence_over_default_ones() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/")
+        .match_header("x-tenant", Matcher::Exact("other".to_string()))
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_default_headers(vec![(
+            HeaderName::from_static("x-tenant"),
+            HeaderValue::from_static("prima"),
+        )])
+        .build(server.url().parse()?);
+
+    RestRequest::new(&bridge)
+        .with_custom_header(HeaderName::from_static("x-tenant"), HeaderValue::from_static("other"))
+        .send()
+        .await?;
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn default_query_pairs_are_merged_with_request_ones() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("api_key".to_string(), "secret".to_string()),
+            Matcher::UrlEncoded("lang".to_string(), "en".to_string()),
+            Matcher::UrlEncoded("page".to_string(), "2".to_string()),
+        ]))
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_default_query_pairs(vec![("api_key", "secret"), ("lang", "it")])
+        .build(server.url().parse()?);
+
+    let request = RestRequest::new(&bridge).with_query_pairs(vec![("lang", "en"), ("page", "2")]);
+    assert_eq!(request.get_url().query(), Some("api_key=secret&lang=en&page=2"));
+
+    request.send().await?;
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn default_timeout_is_used_unless_the_request_sets_one() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/")
+        .with_status(200)
+        .with_chunked_body(|writer| {
+            std::thread::sleep(Duration::from_millis(200));
+            writer.write_all(b"{}")
+        })
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_default_timeout(Duration::from_millis(50))
+        .build(server.url().parse()?);
+
+    let request = RestRequest::new(&bridge);
+    assert_eq!(request.get_timeout(), Duration::from_millis(50));
+    let result = request.send().await;
+    assert!(matches!(result, Err(PrimaBridgeError::HttpError { source, .. }) if source.is_timeout()));
+
+    let result = RestRequest::new(&bridge)
+        .set_timeout(Duration::from_secs(5))
+        .send()
+        .await;
+    assert!(result.is_ok());
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::error::Error;
+
+use prima_bridge::prelude::*;
+use prima_bridge::{LoadBalancingPolicy, LoadBalancingStrategy};
+
+// Nothing listens on the discard port, so the connection gets refused
+const REFUSING_ENDPOINT: &str = "http://127.0.0.1:9";
+
+#[tokio::test]
+async fn round_robin_spreads_requests_across_endpoints() -> Result<(), Box<dyn Error>> {
+    let mut first = mockito::Server::new_async().await;
+    let mut second = mockito::Server::new_async().await;
+    let first_mock = first
+        .mock("GET", "/api/users")
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+    let second_mock = second
+        .mock("GET", "/api/users")
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_load_balancing(LoadBalancingPolicy::new(LoadBalancingStrategy::RoundRobin))
+        .build_with_endpoints(vec![
+            format!("{}/api", first.url()).parse()?,
+            format!("{}/api", second.url()).parse()?,
+        ]);
+
+    for _ in 0..4 {
+        RestRequest::new(&bridge).to("users").send().await?;
+    }
+
+    first_mock.assert_async().await;
+    second_mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn duplicate_endpoints_are_balanced_once() -> Result<(), Box<dyn Error>> {
+    let mut first = mockito::Server::new_async().await;
+    let mut second = mockito::Server::new_async().await;
+    let first_mock = first.mock("GET", "/users").expect(2).create_async().await;
+    let second_mock = second.mock("GET", "/users").expect(2).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_load_balancing(LoadBalancingPolicy::new(LoadBalancingStrategy::RoundRobin))
+        .build_with_endpoints(vec![first.url().parse()?, second.url().parse()?, first.url().parse()?]);
+
+    for _ in 0..4 {
+        RestRequest::new(&bridge).to("users").send().await?;
+    }
+
+    first_mock.assert_async().await;
+    second_mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn failover_sends_the_request_to_the_next_endpoint() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/")
+        .match_body("abcde")
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_load_balancing(LoadBalancingPolicy::new(LoadBalancingStrategy::Failover))
+        .build_with_endpoints(vec![REFUSING_ENDPOINT.parse()?, server.url().parse()?]);
+
+    let result: String = Request::post(&bridge)
+        .raw_body("abcde")
+        .send()
+        .await?
+        .get_data(&["hello"])?;
+    assert_eq!("world!", result.as_str());
+
+    // The refusing endpoint has been ejected, so the request goes straight to the healthy one
+    Request::post(&bridge).raw_body("abcde").send().await?;
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn unhealthy_endpoints_get_ejected() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(200).expect(3).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_load_balancing(LoadBalancingPolicy::new(LoadBalancingStrategy::RoundRobin).with_ejection_threshold(1))
+        .build_with_endpoints(vec![REFUSING_ENDPOINT.parse()?, server.url().parse()?]);
+
+    let result = RestRequest::new(&bridge).send().await;
+    assert!(matches!(result, Err(PrimaBridgeError::HttpError { .. })));
+
+    for _ in 0..3 {
+        RestRequest::new(&bridge).send().await?;
+    }
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[test]
+fn requires_at_least_one_endpoint() {
+    let result = Bridge::builder().try_build_with_endpoints(vec![]);
+
+    assert!(matches!(result, Err(BridgeBuildError::NoEndpoints)));
+}
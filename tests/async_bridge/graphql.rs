@@ -162,6 +162,35 @@ async fn error_response_parser_with_error() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn owned_request_can_be_cloned_and_spawned() -> Result<(), Box<dyn Error>> {
+    let query = "query { hello }";
+    let mut server = Server::new_async().await;
+    let (_m, bridge) = create_gql_bridge(
+        &mut server,
+        200,
+        query,
+        "{\"data\": {\"person\": {\"name\": \"Pippo\"}}}",
+    )
+    .await;
+    let variables: Option<String> = None;
+
+    let request = Request::graphql(&bridge, (query, variables))?.into_owned();
+    let clone = request.try_clone().expect("the body is in memory");
+
+    for request in [request, clone] {
+        let result: Person = tokio::spawn(request.send()).await??.get_data(&["person"])?;
+        assert_eq!(
+            Person {
+                name: "Pippo".to_string()
+            },
+            result
+        );
+    }
+
+    Ok(())
+}
+
 async fn create_gql_bridge(server: &mut Server, status_code: usize, query: &str, body: &str) -> (Mock, Bridge) {
     let mock = server
         .mock("POST", "/")
@@ -0,0 +1,234 @@
+use std::error::Error;
+
+use mockito::Matcher;
+use reqwest::header::{HeaderValue, AUTHORIZATION, CACHE_CONTROL};
+use reqwest::StatusCode;
+
+use prima_bridge::prelude::*;
+use prima_bridge::HttpCache;
+
+fn bridge(server: &mockito::Server) -> Result<Bridge, Box<dyn Error>> {
+    Ok(Bridge::builder()
+        .with_http_cache(HttpCache::in_memory())
+        .build(server.url().parse()?))
+}
+
+#[tokio::test]
+async fn reuses_the_fresh_responses() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/postcodes")
+        .with_header("cache-control", "max-age=60")
+        .with_header("x-version", "1")
+        .with_body("[\"20121\"]")
+        .expect(1)
+        .create_async()
+        .await;
+    let bridge = bridge(&server)?;
+
+    for _ in 0..2 {
+        let response = RestRequest::new(&bridge).to("postcodes").send().await?;
+        assert_eq!(response.status_code(), &StatusCode::OK);
+        assert_eq!(response.headers().get("x-version").unwrap(), "1");
+        assert_eq!(response.get_data::<Vec<String>>(&[])?, vec!["20121".to_string()]);
+    }
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn revalidates_the_stale_responses_with_their_etag() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let first_mock = server
+        .mock("GET", "/catalogue")
+        .match_header("if-none-match", Matcher::Missing)
+        .with_header("cache-control", "no-cache")
+        .with_header("etag", "\"v1\"")
+        .with_body("{\"models\": 3}")
+        .expect(1)
+        .create_async()
+        .await;
+    let not_modified_mock = server
+        .mock("GET", "/catalogue")
+        .match_header("if-none-match", "\"v1\"")
+        .with_status(304)
+        .with_header("x-checked", "true")
+        .expect(2)
+        .create_async()
+        .await;
+    let bridge = bridge(&server)?;
+
+    for _ in 0..3 {
+        let response = RestRequest::new(&bridge).to("catalogue").send().await?;
+        assert_eq!(response.status_code(), &StatusCode::OK);
+        assert_eq!(response.get_data::<u32>(&["models"])?, 3);
+    }
+
+    first_mock.assert_async().await;
+    not_modified_mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn revalidates_the_stale_responses_with_their_last_modified_date() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let last_modified = "Wed, 21 Oct 2015 07:28:00 GMT";
+    let _first_mock = server
+        .mock("GET", "/catalogue")
+        .match_header("if-modified-since", Matcher::Missing)
+        .with_header("last-modified", last_modified)
+        .with_body("{\"models\": 3}")
+        .create_async()
+        .await;
+    let modified_mock = server
+        .mock("GET", "/catalogue")
+        .match_header("if-modified-since", last_modified)
+        .with_body("{\"models\": 4}")
+        .expect(1)
+        .create_async()
+        .await;
+    let bridge = bridge(&server)?;
+
+    let response = RestRequest::new(&bridge).to("catalogue").send().await?;
+    assert_eq!(response.get_data::<u32>(&["models"])?, 3);
+    let response = RestRequest::new(&bridge).to("catalogue").send().await?;
+    assert_eq!(response.get_data::<u32>(&["models"])?, 4);
+
+    modified_mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_store_the_responses_that_forbid_it() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/quotes")
+        .with_header("cache-control", "no-store, max-age=60")
+        .with_header("etag", "\"v1\"")
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+    let bridge = bridge(&server)?;
+
+    for _ in 0..2 {
+        RestRequest::new(&bridge).to("quotes").send().await?;
+    }
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_store_the_private_responses() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/quotes")
+        .with_header("cache-control", "private, max-age=60")
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+    let bridge = bridge(&server)?;
+
+    for _ in 0..2 {
+        RestRequest::new(&bridge).to("quotes").send().await?;
+    }
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn stores_the_responses_to_authorized_requests_only_when_public() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let private_mock = server
+        .mock("GET", "/quotes")
+        .with_header("cache-control", "max-age=60")
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+    let public_mock = server
+        .mock("GET", "/postcodes")
+        .with_header("cache-control", "public, max-age=60")
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+    let bridge = bridge(&server)?;
+
+    for token in ["Bearer alice", "Bearer bob"] {
+        for path in ["quotes", "postcodes"] {
+            RestRequest::new(&bridge)
+                .to(path)
+                .with_custom_header(AUTHORIZATION, HeaderValue::from_static(token))
+                .send()
+                .await?;
+        }
+    }
+
+    private_mock.assert_async().await;
+    public_mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn bypasses_the_cache_when_the_request_asks_to() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/postcodes")
+        .with_header("cache-control", "max-age=60")
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+    let bridge = bridge(&server)?;
+
+    RestRequest::new(&bridge).to("postcodes").send().await?;
+    RestRequest::new(&bridge)
+        .to("postcodes")
+        .with_custom_header(CACHE_CONTROL, HeaderValue::from_static("no-cache"))
+        .send()
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn checks_the_status_code_of_a_failed_revalidation() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _first_mock = server
+        .mock("GET", "/catalogue")
+        .match_header("if-none-match", Matcher::Missing)
+        .with_header("etag", "\"v1\"")
+        .with_body("{}")
+        .create_async()
+        .await;
+    let _failed_mock = server
+        .mock("GET", "/catalogue")
+        .match_header("if-none-match", "\"v1\"")
+        .with_status(503)
+        .create_async()
+        .await;
+    let bridge = bridge(&server)?;
+
+    RestRequest::new(&bridge).to("catalogue").send().await?;
+    let result = RestRequest::new(&bridge).to("catalogue").send().await;
+
+    assert!(matches!(
+        result,
+        Err(PrimaBridgeError::WrongStatusCode(_, StatusCode::SERVICE_UNAVAILABLE))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use std::error::Error;
+use std::time::Duration;
+
+use mockito::Matcher;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Method;
+
+use prima_bridge::{prelude::*, IdempotencyKeys, RetryPolicy};
+
+fn fast_retry_policy() -> RetryPolicy {
+    RetryPolicy::default()
+        .with_jitter(false)
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+}
+
+#[tokio::test]
+async fn retries_with_the_same_key_generated_from_the_request_id() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let bridge = Bridge::builder()
+        .with_retry_policy(fast_retry_policy())
+        .build(server.url().parse()?);
+    let request = Request::post(&bridge).raw_body("abcde").with_idempotency_key();
+    let key = request.get_id().to_string();
+    let unavailable = server
+        .mock("POST", "/")
+        .match_header("idempotency-key", key.as_str())
+        .with_status(503)
+        .expect(2)
+        .create_async()
+        .await;
+    let available = server
+        .mock("POST", "/")
+        .match_header("idempotency-key", key.as_str())
+        .match_body("abcde")
+        .with_body("{\"paid\": true}")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let paid: bool = request.send().await?.get_data(&["paid"])?;
+
+    assert!(paid);
+    unavailable.assert_async().await;
+    available.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn sends_the_custom_key() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("PATCH", "/payments/42")
+        .match_header("idempotency-key", "payment-42")
+        .expect(1)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder()
+        .with_idempotency_keys(IdempotencyKeys::new())
+        .build(server.url().parse()?);
+
+    RestRequest::new(&bridge)
+        .method(Method::PATCH)
+        .to("payments/42")
+        .with_custom_idempotency_key(HeaderValue::from_static("payment-42"))
+        .send()
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn adds_a_key_to_the_requests_with_the_configured_methods() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let bridge = Bridge::builder()
+        .with_idempotency_keys(IdempotencyKeys::new())
+        .build(server.url().parse()?);
+    let post = Request::post(&bridge);
+    let post_mock = server
+        .mock("POST", "/")
+        .match_header("idempotency-key", post.get_id().to_string().as_str())
+        .expect(1)
+        .create_async()
+        .await;
+    let get_mock = server
+        .mock("GET", "/")
+        .match_header("idempotency-key", Matcher::Missing)
+        .expect(1)
+        .create_async()
+        .await;
+
+    post.send().await?;
+    Request::get(&bridge).send().await?;
+
+    post_mock.assert_async().await;
+    get_mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn keeps_the_key_set_as_a_header() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/")
+        .match_header("idempotency-key", "payment-42")
+        .with_status(503)
+        .expect(2)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder()
+        .with_idempotency_keys(IdempotencyKeys::new())
+        .with_retry_policy(fast_retry_policy().with_max_attempts(2))
+        .build(server.url().parse()?);
+
+    let result = Request::post(&bridge)
+        .with_custom_header(
+            HeaderName::from_static("idempotency-key"),
+            HeaderValue::from_static("payment-42"),
+        )
+        .send()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(PrimaBridgeError::RetriesExhausted { attempts: 2, .. })
+    ));
+    mock.assert_async().await;
+
+    Ok(())
+}
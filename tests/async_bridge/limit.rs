@@ -0,0 +1,117 @@
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+use mockito::{Mock, Server};
+
+use prima_bridge::{prelude::*, ConcurrencyLimit, RateLimit};
+
+async fn slow_mock(server: &mut Server, delay: Duration) -> Mock {
+    server
+        .mock("GET", "/")
+        .with_status(200)
+        .with_chunked_body(move |writer| {
+            std::thread::sleep(delay);
+            writer.write_all(b"{\"hello\": \"world!\"}")
+        })
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn rate_limit_fails_fast_when_exhausted() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(200).expect(2).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_rate_limit(RateLimit::new(2, Duration::from_secs(60)).fail_fast())
+        .build(server.url().parse()?);
+
+    RestRequest::new(&bridge).send().await?;
+    RestRequest::new(&bridge).send().await?;
+    let result = RestRequest::new(&bridge).send().await;
+
+    assert!(matches!(result, Err(PrimaBridgeError::RateLimitExceeded(_))));
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rate_limit_is_shared_across_clones() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server.mock("GET", "/").with_status(200).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_rate_limit(RateLimit::new(1, Duration::from_secs(60)).fail_fast())
+        .build(server.url().parse()?);
+    let cloned_bridge = bridge.clone();
+
+    RestRequest::new(&bridge).send().await?;
+    let result = RestRequest::new(&cloned_bridge).send().await;
+
+    assert!(matches!(result, Err(PrimaBridgeError::RateLimitExceeded(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rate_limit_waits_for_the_next_token() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(200).expect(3).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_rate_limit(RateLimit::new(1, Duration::from_millis(100)))
+        .build(server.url().parse()?);
+
+    let start = Instant::now();
+    for _ in 0..3 {
+        RestRequest::new(&bridge).send().await?;
+    }
+
+    assert!(start.elapsed() >= Duration::from_millis(190));
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn concurrency_limit_fails_fast_when_no_slot_is_free() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = slow_mock(&mut server, Duration::from_millis(200)).await;
+
+    let bridge = Bridge::builder()
+        .with_max_concurrency(ConcurrencyLimit::new(1).fail_fast())
+        .build(server.url().parse()?);
+
+    let slow_request = RestRequest::new(&bridge).send();
+    let rejected_request = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        RestRequest::new(&bridge).send().await
+    };
+    let (slow_result, rejected_result) = tokio::join!(slow_request, rejected_request);
+
+    assert!(slow_result.is_ok());
+    assert!(matches!(
+        rejected_result,
+        Err(PrimaBridgeError::ConcurrencyLimitExceeded(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn concurrency_limit_waits_for_a_free_slot() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = slow_mock(&mut server, Duration::from_millis(100)).await;
+
+    let bridge = Bridge::builder().with_max_concurrency(1).build(server.url().parse()?);
+
+    let start = Instant::now();
+    let (first, second) = tokio::join!(RestRequest::new(&bridge).send(), RestRequest::new(&bridge).send());
+
+    assert!(first.is_ok());
+    assert!(second.is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(200));
+
+    Ok(())
+}
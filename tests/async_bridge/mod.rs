@@ -1,4 +1,22 @@
+mod batch;
+mod builder;
+mod circuit_breaker;
+mod coalescing;
+mod codec;
+mod config;
+mod connection;
+mod defaults;
+mod endpoints;
 mod graphql;
 mod graphql_multipart;
+#[cfg(feature = "http-cache")]
+mod http_cache;
+mod idempotency;
+mod limit;
+mod pagination;
 mod rest;
 mod rest_multipart;
+mod retry;
+mod sse;
+mod streaming;
+mod tls;
@@ -0,0 +1,188 @@
+use std::error::Error;
+
+use futures::{StreamExt, TryStreamExt};
+use mockito::Matcher;
+use serde::Deserialize;
+
+use prima_bridge::prelude::*;
+use prima_bridge::Pagination;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Item {
+    id: u32,
+}
+
+fn items(ids: &[u32]) -> Vec<Item> {
+    ids.iter().map(|id| Item { id: *id }).collect()
+}
+
+#[tokio::test]
+async fn follows_the_next_links() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _first_page = server
+        .mock("GET", "/api/items")
+        .match_query(Matcher::UrlEncoded("sort".into(), "id".into()))
+        .with_header(
+            "link",
+            &format!(r#"<{}/api/items?sort=id&page=2>; rel="next""#, server.url()),
+        )
+        .with_body(r#"[{"id": 1}, {"id": 2}]"#)
+        .create_async()
+        .await;
+    let _last_page = server
+        .mock("GET", "/api/items")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("sort".into(), "id".into()),
+            Matcher::UrlEncoded("page".into(), "2".into()),
+        ]))
+        .with_header("link", r#"</api/items?sort=id&page=1>; rel="prev""#)
+        .with_body(r#"[{"id": 3}]"#)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(format!("{}/api", server.url()).parse()?);
+
+    let items: Vec<Item> = RestRequest::new(&bridge)
+        .to("items")
+        .with_query_pair("sort", "id")
+        .paginate(Pagination::link_header())
+        .try_collect()
+        .await?;
+
+    assert_eq!(items, self::items(&[1, 2, 3]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_follow_next_links_out_of_the_endpoint() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _page = server
+        .mock("GET", "/items")
+        .with_header("link", r#"<https://example.com/items?page=2>; rel="next""#)
+        .with_body(r#"[{"id": 1}]"#)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let mut items = RestRequest::new(&bridge)
+        .to("items")
+        .paginate::<Item>(Pagination::link_header());
+
+    assert_eq!(items.next().await.unwrap()?, Item { id: 1 });
+    assert!(matches!(
+        items.next().await,
+        Some(Err(PrimaBridgeError::PaginationError { .. }))
+    ));
+    assert!(items.next().await.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn sends_the_cursor_of_the_previous_page() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _first_page = server
+        .mock("GET", "/items")
+        .match_query(Matcher::Exact(String::new()))
+        .with_body(r#"{"data": [{"id": 1}], "meta": {"next": "abc"}}"#)
+        .create_async()
+        .await;
+    let _last_page = server
+        .mock("GET", "/items")
+        .match_query(Matcher::UrlEncoded("cursor".into(), "abc".into()))
+        .with_body(r#"{"data": [{"id": 2}], "meta": {"next": null}}"#)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let items: Vec<Item> = RestRequest::new(&bridge)
+        .to("items")
+        .paginate(Pagination::cursor(&["meta", "next"], "cursor").with_items(&["data"]))
+        .try_collect()
+        .await?;
+
+    assert_eq!(items, self::items(&[1, 2]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn pages_by_offset_up_to_the_max_pages() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mut pages = vec![];
+    for (offset, body) in [(0, r#"[{"id": 1}, {"id": 2}]"#), (2, r#"[{"id": 3}, {"id": 4}]"#)] {
+        let page = server
+            .mock("GET", "/items")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("offset".into(), offset.to_string()),
+                Matcher::UrlEncoded("limit".into(), "2".into()),
+            ]))
+            .with_body(body)
+            .create_async()
+            .await;
+        pages.push(page);
+    }
+    let unrequested_page = server
+        .mock("GET", "/items")
+        .match_query(Matcher::UrlEncoded("offset".into(), "4".into()))
+        .with_body(r#"[{"id": 5}]"#)
+        .expect(0)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let items: Vec<Item> = RestRequest::new(&bridge)
+        .to("items")
+        .paginate(Pagination::offset("offset", "limit", 2).with_max_pages(2))
+        .try_collect()
+        .await?;
+
+    assert_eq!(items, self::items(&[1, 2, 3, 4]));
+    for page in pages {
+        page.assert_async().await;
+    }
+    unrequested_page.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn prefetches_pages_by_number_until_a_partial_one() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mut pages = vec![];
+    for (page, body) in [
+        (1, r#"[{"id": 1}, {"id": 2}]"#),
+        (2, r#"[{"id": 3}, {"id": 4}]"#),
+        (3, r#"[{"id": 5}]"#),
+    ] {
+        let page = server
+            .mock("GET", "/items")
+            .match_query(Matcher::UrlEncoded("page".into(), page.to_string()))
+            .with_body(body)
+            .create_async()
+            .await;
+        pages.push(page);
+    }
+    let unrequested_page = server
+        .mock("GET", "/items")
+        .match_query(Matcher::UrlEncoded("page".into(), "4".into()))
+        .with_body("[]")
+        .expect(0)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let items: Vec<Item> = RestRequest::new(&bridge)
+        .to("items")
+        .paginate(Pagination::page_number("page", 1, 2).with_prefetch(2))
+        .try_collect()
+        .await?;
+
+    assert_eq!(items, self::items(&[1, 2, 3, 4, 5]));
+    for page in pages {
+        page.assert_async().await;
+    }
+    unrequested_page.assert_async().await;
+
+    Ok(())
+}
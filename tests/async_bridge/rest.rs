@@ -4,7 +4,7 @@ use reqwest::header::{HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use prima_bridge::{prelude::*, MultipartFile, MultipartFormFileField, RedirectPolicy, RestMultipart};
+use prima_bridge::{prelude::*, ArrayEncoding, MultipartFile, MultipartFormFileField, RedirectPolicy, RestMultipart};
 
 use crate::common::*;
 
@@ -354,3 +354,380 @@ async fn decompresses_gzip_responses() {
         .unwrap();
     assert_eq!(result, "world!");
 }
+
+#[cfg(feature = "brotli")]
+#[tokio::test]
+async fn decompresses_brotli_responses() {
+    use std::io::prelude::*;
+    let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+    encoder.write_all(b"{\"hello\": \"world!\"}").unwrap();
+    let body = encoder.into_inner();
+
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/")
+        .with_status(200)
+        .with_header("Content-Encoding", "br")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder().build(server.url().parse().unwrap());
+
+    let result: String = RestRequest::new(&bridge)
+        .send()
+        .await
+        .unwrap()
+        .get_data(&["hello"])
+        .unwrap();
+    assert_eq!(result, "world!");
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn decompresses_deflate_responses() {
+    use flate2::{write::ZlibEncoder, Compression};
+    use std::io::prelude::*;
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(b"{\"hello\": \"world!\"}").unwrap();
+    let body = encoder.finish().unwrap();
+
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/")
+        .with_status(200)
+        .with_header("Content-Encoding", "deflate")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder().build(server.url().parse().unwrap());
+
+    let result: String = RestRequest::new(&bridge)
+        .send()
+        .await
+        .unwrap()
+        .get_data(&["hello"])
+        .unwrap();
+    assert_eq!(result, "world!");
+}
+
+#[cfg(feature = "zstd")]
+#[tokio::test]
+async fn decompresses_zstd_responses() {
+    let body = zstd::encode_all(&b"{\"hello\": \"world!\"}"[..], 0).unwrap();
+
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/")
+        .with_status(200)
+        .with_header("Content-Encoding", "zstd")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder().build(server.url().parse().unwrap());
+
+    let result: String = RestRequest::new(&bridge)
+        .send()
+        .await
+        .unwrap()
+        .get_data(&["hello"])
+        .unwrap();
+    assert_eq!(result, "world!");
+}
+
+#[cfg(feature = "gzip")]
+#[tokio::test]
+async fn compresses_large_request_bodies_with_gzip() -> Result<(), Box<dyn Error>> {
+    use prima_bridge::RequestCompression;
+    use std::io::prelude::*;
+
+    let payload = json!({ "items": vec!["item"; 100] });
+    let expected = payload.clone();
+    let mut server = mockito::Server::new_async().await;
+    let compressed_mock = server
+        .mock("POST", "/large")
+        .match_header("content-encoding", "gzip")
+        .match_request(move |request| {
+            let mut body = String::new();
+            let decoded = request.body().is_ok_and(|body_bytes| {
+                flate2::read::GzDecoder::new(&body_bytes[..])
+                    .read_to_string(&mut body)
+                    .is_ok()
+            });
+            decoded && serde_json::from_str::<serde_json::Value>(&body).is_ok_and(|body| body == expected)
+        })
+        .with_status(200)
+        .create_async()
+        .await;
+    let plain_mock = server
+        .mock("POST", "/small")
+        .match_header("content-encoding", mockito::Matcher::Missing)
+        .match_body(mockito::Matcher::Json(json!({"hello": "world!"})))
+        .with_status(200)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder()
+        .with_request_compression(RequestCompression::gzip().with_min_size(512))
+        .build(server.url().parse()?);
+
+    Request::post(&bridge).to("large").json_body(&payload)?.send().await?;
+    Request::post(&bridge)
+        .to("small")
+        .json_body(&json!({"hello": "world!"}))?
+        .send()
+        .await?;
+
+    compressed_mock.assert_async().await;
+    plain_mock.assert_async().await;
+
+    Ok(())
+}
+
+#[cfg(feature = "zstd")]
+#[tokio::test]
+async fn compresses_request_bodies_with_zstd() -> Result<(), Box<dyn Error>> {
+    use prima_bridge::RequestCompression;
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/")
+        .match_header("content-encoding", "zstd")
+        .match_request(|request| {
+            request
+                .body()
+                .is_ok_and(|body| zstd::decode_all(&body[..]).is_ok_and(|body| body == b"hello, world!"))
+        })
+        .with_status(200)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder()
+        .with_request_compression(RequestCompression::zstd().with_min_size(0))
+        .build(server.url().parse()?);
+
+    Request::post(&bridge).raw_body("hello, world!").send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+fn owned_request(bridge: &Bridge, id: u32) -> RestRequest<'static, reqwest::Client> {
+    RestRequest::new(bridge)
+        .to(format!("people/{id}"))
+        .with_query_pair("fields", String::from("name"))
+        .into_owned()
+}
+
+#[tokio::test]
+async fn owned_request_can_be_sent_from_a_spawned_task() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/people/42")
+        .match_query(mockito::Matcher::UrlEncoded("fields".to_string(), "name".to_string()))
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let request = owned_request(&bridge, 42);
+    drop(bridge);
+    let result: String = tokio::spawn(request.send()).await??.get_data(&["hello"])?;
+
+    assert_eq!("world!", result.as_str());
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn try_clone_clones_requests_with_in_memory_bodies() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/")
+        .match_body(mockito::Matcher::Json(json!({"hello": "world!"})))
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let request = Request::post(&bridge).json_body(&Data {
+        hello: "world!".to_string(),
+    })?;
+    let clone = request.try_clone().expect("the body is in memory");
+
+    assert_ne!(request.get_id(), clone.get_id());
+    request.send().await?;
+    clone.send().await?;
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn try_clone_fails_when_body_is_stream() -> Result<(), Box<dyn Error>> {
+    let bridge = Bridge::builder().build("http://prima.it".parse()?);
+
+    let file = tokio::fs::File::open("tests/resources/howdy_world.txt").await?;
+    let request = RestRequest::new(&bridge).raw_body(file);
+
+    assert!(request.try_clone().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn to_template_percent_encodes_the_parameters() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let (mock, bridge) = server
+        .create_bridge_with_base_and_path(200, "{\"hello\": \"world!\"}", "api", "users/john%2Fdoe%3F/orders/42")
+        .await;
+
+    let result: String = RestRequest::new(&bridge)
+        .to_template("/users/{id}/orders/{order}", [("id", "john/doe?"), ("order", "42")])?
+        .send()
+        .await?
+        .get_data(&["hello"])?;
+
+    assert_eq!("world!", result.as_str());
+    mock.assert_async().await;
+
+    let result = RestRequest::new(&bridge).to_template("/users/{id}", [("user", "john")]);
+    assert!(matches!(result, Err(PrimaBridgeError::InvalidPathTemplate { .. })));
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct Search {
+    name: String,
+    tags: Vec<&'static str>,
+    page: Page,
+}
+
+#[derive(Serialize)]
+struct Page {
+    size: u32,
+    cursor: Option<String>,
+}
+
+/// Whether the urlencoded string holds exactly the given pairs, in any order.
+fn has_pairs(urlencoded: &[u8], pairs: &[(&str, &str)]) -> bool {
+    let mut actual: Vec<(String, String)> = form_urlencoded::parse(urlencoded).into_owned().collect();
+    let mut expected: Vec<(String, String)> = pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    actual.sort();
+    expected.sort();
+    actual == expected
+}
+
+fn search() -> Search {
+    Search {
+        name: "john doe".to_string(),
+        tags: vec!["a", "b"],
+        page: Page { size: 10, cursor: None },
+    }
+}
+
+#[tokio::test]
+async fn with_query_serializes_nested_structs() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/")
+        .match_query(mockito::Matcher::Any)
+        .match_request(|request| {
+            let query = request.path_and_query().split_once('?').map_or("", |(_, query)| query);
+            has_pairs(
+                query.as_bytes(),
+                &[
+                    ("name", "john doe"),
+                    ("tags[]", "a"),
+                    ("tags[]", "b"),
+                    ("page[size]", "10"),
+                ],
+            )
+        })
+        .with_status(200)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    RestRequest::new(&bridge).with_query(&search())?.send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn form_body_sets_an_urlencoded_body() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/")
+        .match_header("content-type", "application/x-www-form-urlencoded")
+        .match_request(|request| {
+            request.body().is_ok_and(|body| {
+                has_pairs(
+                    body,
+                    &[("name", "john doe"), ("tags", "a"), ("tags", "b"), ("page[size]", "10")],
+                )
+            })
+        })
+        .with_status(200)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    Request::post(&bridge)
+        .form_body_encoded(&search(), ArrayEncoding::Repeated)?
+        .send()
+        .await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rejects_responses_larger_than_the_limit() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _sized_mock = server
+        .mock("GET", "/sized")
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .create_async()
+        .await;
+    // Without a content-length, the limit is only reached while reading the body
+    let _chunked_mock = server
+        .mock("GET", "/chunked")
+        .with_status(200)
+        .with_chunked_body(|writer| writer.write_all(b"{\"hello\": \"world!\"}"))
+        .create_async()
+        .await;
+    let bridge = Bridge::builder()
+        .with_max_response_bytes(10)
+        .build(server.url().parse()?);
+
+    for path in ["sized", "chunked"] {
+        let result = RestRequest::new(&bridge).to(path).send().await;
+        assert!(
+            matches!(result, Err(PrimaBridgeError::ResponseTooLarge { limit: 10, .. })),
+            "{path}: {result:?}"
+        );
+    }
+
+    let result: String = RestRequest::new(&bridge)
+        .to("chunked")
+        .with_max_response_bytes(1024)
+        .send()
+        .await?
+        .get_data(&["hello"])?;
+    assert_eq!(result, "world!");
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use mockito::{Matcher, Server};
 use prima_bridge::prelude::*;
-use prima_bridge::{MultipartFile, MultipartFormFileField, RestMultipart};
+use prima_bridge::{MultipartFile, MultipartFormFileField, MultipartPart, RestMultipart};
+use reqwest::header::{HeaderName, HeaderValue};
 use std::error::Error;
 
 #[tokio::test]
@@ -94,6 +95,50 @@ async fn multipart_rest_multi_file() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn multipart_rest_text_and_json_parts() -> Result<(), Box<dyn Error>> {
+    let re_file = r#"Content-Disposition: form-data; name="upload"; filename="hello_world\.txt"\s+Content-Type: text/plain\s+Hello, world!"#;
+    let re_text = r#"Content-Disposition: form-data; name="description"\s+A greeting"#;
+    let re_json = r#"Content-Disposition: form-data; name="manifest"\s+Content-Type: application/json\s+x-checksum: 6cd3556d\s+\{"files":1\}"#;
+
+    let mut server = Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/")
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .match_header("Content-Type", Matcher::Regex(r#"^multipart/form-data"#.to_string()))
+        .match_body(Matcher::AllOf(vec![
+            Matcher::Regex(re_file.to_string()),
+            Matcher::Regex(re_text.to_string()),
+            Matcher::Regex(re_json.to_string()),
+        ]))
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder().build(server.url().parse().unwrap());
+
+    let multipart = RestMultipart::single(
+        "upload",
+        MultipartFile::new(b"Hello, world!".to_vec())
+            .with_name("hello_world.txt")
+            .with_mime_type("text/plain"),
+    )
+    .with_text("description", "A greeting")
+    .with_part(
+        MultipartPart::json("manifest", &serde_json::json!({"files": 1}))?.with_header(
+            HeaderName::from_static("x-checksum"),
+            HeaderValue::from_static("6cd3556d"),
+        ),
+    );
+
+    Request::post(&bridge).multipart_body(multipart).send().await?;
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn multipart_rest_single_file_stream() {
     let mut server = Server::new_async().await;
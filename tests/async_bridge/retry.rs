@@ -0,0 +1,207 @@
+use std::error::Error;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use prima_bridge::{prelude::*, RetryPolicy};
+
+fn fast_retry_policy() -> RetryPolicy {
+    RetryPolicy::default()
+        .with_jitter(false)
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+}
+
+#[tokio::test]
+async fn retries_on_retryable_status_code() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let unavailable = server.mock("GET", "/").with_status(503).expect(2).create_async().await;
+    let available = server
+        .mock("GET", "/")
+        .with_status(200)
+        .with_body("{\"hello\": \"world!\"}")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder()
+        .with_retry_policy(fast_retry_policy())
+        .build(server.url().parse()?);
+
+    let result: String = RestRequest::new(&bridge).send().await?.get_data(&["hello"])?;
+
+    assert_eq!("world!", result.as_str());
+    unavailable.assert_async().await;
+    available.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reports_attempts_when_retries_are_exhausted() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(503).expect(4).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_retry_policy(fast_retry_policy().with_max_attempts(4))
+        .build(server.url().parse()?);
+
+    let result = RestRequest::new(&bridge).send().await;
+
+    match result {
+        Err(PrimaBridgeError::RetriesExhausted { attempts, source }) => {
+            assert_eq!(attempts, 4);
+            assert!(matches!(
+                *source,
+                PrimaBridgeError::WrongStatusCode(_, StatusCode::SERVICE_UNAVAILABLE)
+            ));
+        }
+        other => panic!("expected RetriesExhausted, got: {other:?}"),
+    }
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_retry_non_retryable_status_code() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(404).expect(1).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_retry_policy(fast_retry_policy())
+        .build(server.url().parse()?);
+
+    let result = RestRequest::new(&bridge).send().await;
+
+    assert!(matches!(
+        result,
+        Err(PrimaBridgeError::WrongStatusCode(_, StatusCode::NOT_FOUND))
+    ));
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_the_non_retryable_error_following_a_retry_unchanged() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let unavailable = server.mock("GET", "/").with_status(503).expect(1).create_async().await;
+    let bad_request = server.mock("GET", "/").with_status(400).expect(1).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_retry_policy(fast_retry_policy())
+        .build(server.url().parse()?);
+
+    let result = RestRequest::new(&bridge).send().await;
+
+    assert!(matches!(
+        result,
+        Err(PrimaBridgeError::WrongStatusCode(_, StatusCode::BAD_REQUEST))
+    ));
+    unavailable.assert_async().await;
+    bad_request.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_retry_non_idempotent_methods_by_default() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("POST", "/").with_status(503).expect(1).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_retry_policy(fast_retry_policy())
+        .build(server.url().parse()?);
+
+    let result = Request::post(&bridge).raw_body("abcde").send().await;
+
+    assert!(matches!(result, Err(PrimaBridgeError::WrongStatusCode(..))));
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retries_non_idempotent_methods_when_allowed_and_replays_the_body() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/")
+        .match_body("abcde")
+        .with_status(503)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let result = Request::post(&bridge)
+        .raw_body("abcde")
+        .with_retry_policy(fast_retry_policy().with_idempotent_only(false))
+        .send()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(PrimaBridgeError::RetriesExhausted { attempts: 3, .. })
+    ));
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn request_retry_policy_overrides_bridge_one() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(503).expect(1).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_retry_policy(fast_retry_policy())
+        .build(server.url().parse()?);
+
+    let result = RestRequest::new(&bridge)
+        .with_retry_policy(RetryPolicy::disabled())
+        .send()
+        .await;
+
+    assert!(matches!(result, Err(PrimaBridgeError::WrongStatusCode(..))));
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_last_response_when_ignoring_status_code() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("GET", "/").with_status(429).expect(2).create_async().await;
+
+    let bridge = Bridge::builder()
+        .with_retry_policy(fast_retry_policy().with_max_attempts(2))
+        .build(server.url().parse()?);
+
+    let response = RestRequest::new(&bridge).ignore_status_code().send().await?;
+
+    assert_eq!(response.status_code(), &StatusCode::TOO_MANY_REQUESTS);
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retries_connection_errors() -> Result<(), Box<dyn Error>> {
+    // Nothing listens on the discard port, so the connection gets refused
+    let bridge = Bridge::builder()
+        .with_retry_policy(fast_retry_policy())
+        .build("http://127.0.0.1:9".parse()?);
+
+    let result = RestRequest::new(&bridge).send().await;
+
+    match result {
+        Err(PrimaBridgeError::RetriesExhausted { attempts, source }) => {
+            assert_eq!(attempts, 3);
+            assert!(matches!(*source, PrimaBridgeError::HttpError { .. }));
+        }
+        other => panic!("expected RetriesExhausted, got: {other:?}"),
+    }
+
+    Ok(())
+}
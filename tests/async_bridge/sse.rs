@@ -0,0 +1,219 @@
+use std::error::Error;
+use std::time::Duration;
+
+use futures::StreamExt;
+use mockito::Matcher;
+use serde::Deserialize;
+
+use prima_bridge::prelude::*;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Progress {
+    done: u32,
+}
+
+#[tokio::test]
+async fn streams_the_events_and_resumes_after_the_last_one() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let first_mock = server
+        .mock("GET", "/progress")
+        .match_header("accept", "text/event-stream")
+        .match_header("last-event-id", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(": started\nid: 1\nretry: 10\nevent: progress\ndata: {\"done\": 50}\n\n")
+        .create_async()
+        .await;
+    let second_mock = server
+        .mock("GET", "/progress")
+        .match_header("last-event-id", "1")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body("id: 2\nevent: progress\ndata: {\"done\": 100}\n\n")
+        .create_async()
+        .await;
+    let last_mock = server
+        .mock("GET", "/progress")
+        .match_header("last-event-id", "2")
+        .with_status(204)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let events: Vec<_> = RestRequest::new(&bridge)
+        .to("progress")
+        .send_sse()
+        .await?
+        .collect()
+        .await;
+    let events = events.into_iter().collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].id(), Some("1"));
+    assert_eq!(events[0].event(), "progress");
+    assert_eq!(events[0].json::<Progress>()?, Progress { done: 50 });
+    assert_eq!(events[1].id(), Some("2"));
+    assert_eq!(events[1].json::<Progress>()?, Progress { done: 100 });
+
+    first_mock.assert_async().await;
+    second_mock.assert_async().await;
+    last_mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ends_with_the_error_of_a_failed_reconnection() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _first_mock = server
+        .mock("GET", "/progress")
+        .match_header("last-event-id", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body("id: 1\nretry: 10\ndata: started\n\n")
+        .create_async()
+        .await;
+    let _failed_mock = server
+        .mock("GET", "/progress")
+        .match_header("last-event-id", "1")
+        .with_status(401)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let mut events = RestRequest::new(&bridge).to("progress").send_sse().await?;
+
+    assert_eq!(events.next().await.unwrap()?.data(), "started");
+    assert!(matches!(
+        events.next().await,
+        Some(Err(PrimaBridgeError::WrongStatusCode(
+            _,
+            reqwest::StatusCode::UNAUTHORIZED
+        )))
+    ));
+    assert!(events.next().await.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reconnects_after_the_delay_of_a_retry_only_block() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _first_mock = server
+        .mock("GET", "/progress")
+        .match_header("last-event-id", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body("retry: 10\n\nid: 1\ndata: started\n\n")
+        .create_async()
+        .await;
+    let _resumed_mock = server
+        .mock("GET", "/progress")
+        .match_header("last-event-id", "1")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body("id: 2\ndata: done\n\n")
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let mut events = RestRequest::new(&bridge).to("progress").send_sse().await?;
+
+    let started = events.next().await.unwrap()?;
+    assert_eq!(started.data(), "started");
+    assert_eq!(started.retry(), None);
+    // Way shorter than the default delay of 3 seconds
+    let resumed = tokio::time::timeout(Duration::from_secs(1), events.next()).await?;
+    assert_eq!(resumed.unwrap()?.data(), "done");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ignores_the_default_timeout_of_the_bridge() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/progress")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_chunked_body(|writer| {
+            std::thread::sleep(Duration::from_millis(300));
+            writer.write_all(b"data: late\n\n")
+        })
+        .create_async()
+        .await;
+    let bridge = Bridge::builder()
+        .with_default_timeout(Duration::from_millis(100))
+        .build(server.url().parse()?);
+
+    let mut events = RestRequest::new(&bridge).to("progress").send_sse().await?;
+
+    assert_eq!(events.next().await.unwrap()?.data(), "late");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fails_when_the_stream_cannot_be_opened() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server.mock("GET", "/progress").with_status(404).create_async().await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let result = RestRequest::new(&bridge).to("progress").send_sse().await;
+
+    assert!(matches!(result, Err(PrimaBridgeError::WrongStatusCode(..))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fails_when_the_response_is_not_an_event_stream() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/progress")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("{}")
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let result = RestRequest::new(&bridge).to("progress").send_sse().await;
+
+    assert!(matches!(
+        result,
+        Err(PrimaBridgeError::UnexpectedContentType { content_type: Some(content_type), .. })
+            if content_type == "application/json"
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn stops_reconnecting_to_an_unreachable_server() -> Result<(), Box<dyn Error>> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let url: reqwest::Url = format!("http://{}", listener.local_addr()?).parse()?;
+    let server = std::thread::spawn(move || -> std::io::Result<()> {
+        use std::io::{Read, Write};
+
+        // Serves a single connection, then stops listening
+        let (mut stream, _) = listener.accept()?;
+        let _ = stream.read(&mut [0; 4096])?;
+        stream.write_all(
+            b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\nretry: 1\ndata: started\n\n",
+        )
+    });
+    let bridge = Bridge::builder().build(url);
+
+    let mut events = RestRequest::new(&bridge).to("progress").send_sse().await?;
+    assert_eq!(events.next().await.unwrap()?.data(), "started");
+    server.join().unwrap()?;
+
+    let errors: Vec<_> = tokio::time::timeout(Duration::from_secs(5), events.collect::<Vec<_>>()).await?;
+    assert_eq!(errors.len(), 5);
+    assert!(errors
+        .iter()
+        .all(|error| matches!(error, Err(PrimaBridgeError::HttpError { .. }))));
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use std::error::Error;
+
+use futures::{StreamExt, TryStreamExt};
+use reqwest::StatusCode;
+
+use prima_bridge::prelude::*;
+
+const BODY: &str = "a body long enough to be worth streaming";
+
+#[tokio::test]
+async fn streams_the_response_body() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/export")
+        .with_status(200)
+        .with_header("x-export-id", "42")
+        .with_chunked_body(|writer| {
+            for chunk in BODY.as_bytes().chunks(8) {
+                writer.write_all(chunk)?;
+            }
+            Ok(())
+        })
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let response = RestRequest::new(&bridge).to("export").send_streaming().await?;
+    assert!(response.is_ok());
+    assert_eq!(response.headers().get("x-export-id").unwrap(), "42");
+
+    let chunks: Vec<_> = response.into_stream().try_collect().await?;
+    assert_eq!(chunks.concat(), BODY.as_bytes());
+
+    mock.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn writes_the_response_body_to_a_file() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/export")
+        .with_status(200)
+        .with_body(BODY)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+    let path = std::env::temp_dir().join(format!("bridge-rs-{}.txt", uuid::Uuid::new_v4()));
+
+    let written = RestRequest::new(&bridge)
+        .to("export")
+        .send_streaming()
+        .await?
+        .write_to_file(&path)
+        .await?;
+
+    assert_eq!(written, BODY.len() as u64);
+    assert_eq!(tokio::fs::read_to_string(&path).await?, BODY);
+    tokio::fs::remove_file(&path).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn checks_the_status_code_before_streaming() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/export")
+        .with_status(404)
+        .with_body("not found")
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let result = RestRequest::new(&bridge).to("export").send_streaming().await;
+    assert!(matches!(
+        result,
+        Err(PrimaBridgeError::WrongStatusCode(_, StatusCode::NOT_FOUND))
+    ));
+
+    let mut body = Vec::new();
+    let response = RestRequest::new(&bridge)
+        .to("export")
+        .ignore_status_code()
+        .send_streaming()
+        .await?;
+    assert_eq!(response.status_code(), &StatusCode::NOT_FOUND);
+    response.write_to(&mut body).await?;
+    assert_eq!(body, b"not found");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn stops_streaming_when_the_body_exceeds_the_limit() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/export")
+        .with_status(200)
+        .with_chunked_body(|writer| writer.write_all(BODY.as_bytes()))
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let mut body = Vec::new();
+    let result = RestRequest::new(&bridge)
+        .to("export")
+        .with_max_response_bytes(16)
+        .send_streaming()
+        .await?
+        .write_to(&mut body)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(PrimaBridgeError::ResponseTooLarge { limit: 16, .. })
+    ));
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct Record {
+    id: u32,
+}
+
+#[tokio::test]
+async fn deserializes_json_lines_as_they_are_received() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/export")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_chunked_body(|writer| {
+            for chunk in "{\"id\": 1}\n{\"id\"".as_bytes().chunks(3) {
+                writer.write_all(chunk)?;
+            }
+            writer.write_all(b": 2}\n\n{\"id\": \"three\"}\n")
+        })
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let records: Vec<PrimaBridgeResult<Record>> = RestRequest::new(&bridge)
+        .to("export")
+        .send_streaming()
+        .await?
+        .json_lines()
+        .collect()
+        .await;
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].as_ref().unwrap(), &Record { id: 1 });
+    assert_eq!(records[1].as_ref().unwrap(), &Record { id: 2 });
+    assert!(matches!(
+        &records[2],
+        Err(PrimaBridgeError::DecodeError { path: Some(path), .. }) if path == "id"
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deserializes_the_elements_of_a_json_array_as_they_are_received() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/export")
+        .with_status(200)
+        .with_chunked_body(|writer| {
+            let body = r#"{"total": 3, "page": {"items": [{"id": 1}, {"id": 2}, {"id": 3}]}}"#;
+            for chunk in body.as_bytes().chunks(5) {
+                writer.write_all(chunk)?;
+            }
+            Ok(())
+        })
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let records: Vec<Record> = RestRequest::new(&bridge)
+        .to("export")
+        .send_streaming()
+        .await?
+        .json_array_items(&["page", "items"])
+        .try_collect()
+        .await?;
+
+    assert_eq!(records, vec![Record { id: 1 }, Record { id: 2 }, Record { id: 3 }]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fails_when_there_is_no_json_array_to_stream() -> Result<(), Box<dyn Error>> {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/export")
+        .with_status(200)
+        .with_body(r#"{"items": {"id": 1}}"#)
+        .create_async()
+        .await;
+    let bridge = Bridge::builder().build(server.url().parse()?);
+
+    let result: PrimaBridgeResult<Vec<Record>> = RestRequest::new(&bridge)
+        .to("export")
+        .send()
+        .await?
+        .json_array_items(&["items"])
+        .try_collect()
+        .await;
+
+    assert!(matches!(result, Err(PrimaBridgeError::JsonStreamError { .. })));
+
+    Ok(())
+}
@@ -0,0 +1,232 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use rcgen::{
+    BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, Issuer, KeyPair, KeyUsagePurpose,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::crypto::aws_lc_rs;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{version, RootCertStore, ServerConfig, SupportedProtocolVersion};
+use tokio_rustls::TlsAcceptor;
+
+use prima_bridge::prelude::*;
+use prima_bridge::{TlsIdentity, TlsVersion};
+
+/// A private CA, with a server certificate for 127.0.0.1 and a client certificate signed by it.
+struct Pki {
+    ca_pem: String,
+    ca_der: CertificateDer<'static>,
+    server_der: CertificateDer<'static>,
+    server_key_der: Vec<u8>,
+    client_pem: String,
+}
+
+impl Pki {
+    fn generate() -> Self {
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "bridge.rs test CA");
+        ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::DigitalSignature];
+        let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+        let ca = Issuer::new(ca_params, ca_key);
+
+        let server_key = KeyPair::generate().unwrap();
+        let mut server_params = CertificateParams::new(vec!["127.0.0.1".to_string()]).unwrap();
+        server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        let server_cert = server_params.signed_by(&server_key, &ca).unwrap();
+
+        let client_key = KeyPair::generate().unwrap();
+        let mut client_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        client_params
+            .distinguished_name
+            .push(DnType::CommonName, "bridge.rs test client");
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client_cert = client_params.signed_by(&client_key, &ca).unwrap();
+
+        Self {
+            ca_pem: ca_cert.pem(),
+            ca_der: ca_cert.der().clone(),
+            server_der: server_cert.der().clone(),
+            server_key_der: server_key.serialize_der(),
+            client_pem: format!("{}{}", client_cert.pem(), client_key.serialize_pem()),
+        }
+    }
+
+    /// Starts an HTTPS server answering every request with a JSON body, returning its URL.
+    async fn start_server(
+        &self,
+        require_client_certificate: bool,
+        versions: &[&'static SupportedProtocolVersion],
+    ) -> String {
+        let provider = Arc::new(aws_lc_rs::default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_protocol_versions(versions)
+            .unwrap();
+        let builder = if require_client_certificate {
+            let mut roots = RootCertStore::empty();
+            roots.add(self.ca_der.clone()).unwrap();
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .unwrap();
+            builder.with_client_cert_verifier(verifier)
+        } else {
+            builder.with_no_client_auth()
+        };
+        let config = builder
+            .with_single_cert(
+                vec![self.server_der.clone()],
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(self.server_key_der.clone())),
+            )
+            .unwrap();
+
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(mut stream) = acceptor.accept(stream).await else {
+                        return;
+                    };
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1024];
+                    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        match stream.read(&mut buffer).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(read) => request.extend_from_slice(&buffer[..read]),
+                        }
+                    }
+                    let body = "{\"hello\": \"world!\"}";
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        format!("https://127.0.0.1:{port}")
+    }
+}
+
+#[tokio::test]
+async fn trusts_a_private_root_certificate() -> Result<(), Box<dyn Error>> {
+    let pki = Pki::generate();
+    let url = pki.start_server(false, &[&version::TLS13]).await;
+
+    let bridge = Bridge::builder()
+        .with_root_certificate(&pki.ca_pem)
+        .try_build(url.parse()?)?;
+    let result: String = RestRequest::new(&bridge).send().await?.get_data(&["hello"])?;
+
+    assert_eq!("world!", result.as_str());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rejects_certificates_of_unknown_authorities() -> Result<(), Box<dyn Error>> {
+    let pki = Pki::generate();
+    let url = pki.start_server(false, &[&version::TLS13]).await;
+
+    let bridge = Bridge::builder().try_build(url.parse()?)?;
+    let result = RestRequest::new(&bridge).send().await;
+
+    assert!(matches!(result, Err(PrimaBridgeError::HttpError { .. })));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn accepts_invalid_certificates_when_asked_to() -> Result<(), Box<dyn Error>> {
+    let pki = Pki::generate();
+    let url = pki.start_server(false, &[&version::TLS13]).await;
+
+    let bridge = Bridge::builder()
+        .danger_accept_invalid_certs(true)
+        .try_build(url.parse()?)?;
+    let result: String = RestRequest::new(&bridge).send().await?.get_data(&["hello"])?;
+
+    assert_eq!("world!", result.as_str());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn presents_the_client_certificate_for_mutual_tls() -> Result<(), Box<dyn Error>> {
+    let pki = Pki::generate();
+    let url = pki.start_server(true, &[&version::TLS13]).await;
+
+    let bridge = Bridge::builder()
+        .with_root_certificate(&pki.ca_pem)
+        .with_identity(TlsIdentity::pem(pki.client_pem.as_str()))
+        .try_build(url.parse()?)?;
+    let result: String = RestRequest::new(&bridge).send().await?.get_data(&["hello"])?;
+    assert_eq!("world!", result.as_str());
+
+    let anonymous_bridge = Bridge::builder()
+        .with_root_certificate(&pki.ca_pem)
+        .try_build(url.parse()?)?;
+    let result = RestRequest::new(&anonymous_bridge).send().await;
+    assert!(matches!(result, Err(PrimaBridgeError::HttpError { .. })));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn enforces_the_minimum_tls_version() -> Result<(), Box<dyn Error>> {
+    let pki = Pki::generate();
+    let url = pki.start_server(false, &[&version::TLS12]).await;
+
+    let bridge = Bridge::builder()
+        .with_root_certificate(&pki.ca_pem)
+        .with_tls_min_version(TlsVersion::Tls1_2)
+        .try_build(url.parse()?)?;
+    RestRequest::new(&bridge).send().await?;
+
+    let bridge = Bridge::builder()
+        .with_root_certificate(&pki.ca_pem)
+        .with_tls_min_version(TlsVersion::Tls1_3)
+        .try_build(url.parse()?)?;
+    let result = RestRequest::new(&bridge).send().await;
+    assert!(matches!(result, Err(PrimaBridgeError::HttpError { .. })));
+
+    Ok(())
+}
+
+#[test]
+fn reports_invalid_tls_material() -> Result<(), Box<dyn Error>> {
+    let result = Bridge::builder()
+        .with_root_certificate("not a certificate")
+        .try_build("https://prima.it".parse()?);
+    assert!(matches!(result, Err(BridgeBuildError::Tls(_))));
+
+    let result = Bridge::builder()
+        .with_identity(TlsIdentity::pem("not an identity"))
+        .try_build("https://prima.it".parse()?);
+    assert!(matches!(result, Err(BridgeBuildError::Tls(_))));
+
+    Ok(())
+}
+
+#[cfg(feature = "native-tls")]
+#[test]
+fn reports_invalid_pkcs12_archives() -> Result<(), Box<dyn Error>> {
+    let result = Bridge::builder()
+        .with_identity(TlsIdentity::pkcs12(b"not an archive".to_vec(), "password"))
+        .try_build("https://prima.it".parse()?);
+
+    assert!(matches!(result, Err(BridgeBuildError::Tls(_))));
+
+    Ok(())
+}